@@ -0,0 +1,70 @@
+//! Times the conversions which spend most of their time lowercasing or
+//! uppercasing, on inputs which are already in the target case and on inputs
+//! which are not.
+//!
+//! Run with `cargo bench`. Under `cargo test` each case runs once, as a smoke
+//! test.
+
+use std::time::Instant;
+
+use heck::{ToShoutySnakeCase, ToSnakeCase, ToTitleCase};
+
+const ASCII: &str = "the quick brown fox jumps over the lazy dog ";
+const UNICODE: &str = "größenmaßstäbe ελληνικά кириллица façade ";
+
+fn bench(name: &str, input: &str, iterations: u32, f: fn(&str) -> String) {
+    // Keeping the total length of the output stops the conversions from being
+    // optimized away.
+    let mut len = 0;
+    let start = Instant::now();
+    for _ in 0..iterations {
+        len += f(input).len();
+    }
+    let elapsed = start.elapsed();
+    let per_byte = elapsed.as_nanos() as f64 / (iterations as f64 * input.len() as f64);
+    println!(
+        "{:<32} {:>8.2} ns/byte ({} bytes written)",
+        name, per_byte, len
+    );
+}
+
+fn main() {
+    let iterations = if std::env::args().any(|arg| arg == "--bench") {
+        1_000
+    } else {
+        1
+    };
+
+    let ascii_lower = ASCII.repeat(100);
+    let ascii_upper = ascii_lower.to_uppercase();
+    let unicode_lower = UNICODE.repeat(100);
+    let unicode_upper = unicode_lower.to_uppercase();
+
+    let snake: fn(&str) -> String = |s| s.to_snake_case();
+    let shouty_snake: fn(&str) -> String = |s| s.to_shouty_snake_case();
+    let title: fn(&str) -> String = |s| s.to_title_case();
+
+    let cases = [
+        ("snake, ascii lowercase", &ascii_lower, snake),
+        ("snake, ascii uppercase", &ascii_upper, snake),
+        ("snake, unicode lowercase", &unicode_lower, snake),
+        ("snake, unicode uppercase", &unicode_upper, snake),
+        ("shouty snake, ascii uppercase", &ascii_upper, shouty_snake),
+        ("shouty snake, ascii lowercase", &ascii_lower, shouty_snake),
+        (
+            "shouty snake, unicode uppercase",
+            &unicode_upper,
+            shouty_snake,
+        ),
+        (
+            "shouty snake, unicode lowercase",
+            &unicode_lower,
+            shouty_snake,
+        ),
+        ("title, ascii lowercase", &ascii_lower, title),
+        ("title, unicode lowercase", &unicode_lower, title),
+    ];
+    for (name, input, f) in cases {
+        bench(name, input, iterations, f);
+    }
+}
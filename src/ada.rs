@@ -0,0 +1,120 @@
+use core::fmt;
+
+use alloc::{
+    borrow::ToOwned,
+    string::{String, ToString},
+};
+
+use crate::{capitalize, transform, Options, WithOptions};
+
+/// This trait defines an Ada case conversion.
+///
+/// In Ada_Case, word boundaries are indicated by underscores and words start
+/// with Capital Letters. It is the naming convention of the Ada programming
+/// language.
+///
+/// ## Example:
+///
+/// ```rust
+/// use heck::ToAdaCase;
+///
+/// let sentence = "We, the workers, can build others to take their place.";
+/// assert_eq!(sentence.to_ada_case(), "We_The_Workers_Can_Build_Others_To_Take_Their_Place");
+/// ```
+pub trait ToAdaCase: ToOwned {
+    /// Convert this type to Ada_Case.
+    fn to_ada_case(&self) -> Self::Owned;
+}
+
+impl ToAdaCase for str {
+    fn to_ada_case(&self) -> Self::Owned {
+        AsAdaCase(self).to_string()
+    }
+}
+
+/// This wrapper performs an Ada case conversion in [`fmt::Display`].
+///
+/// ## Example:
+///
+/// ```
+/// use heck::AsAdaCase;
+///
+/// let sentence = "We, the workers, can build others to take their place.";
+/// assert_eq!(format!("{}", AsAdaCase(sentence)), "We_The_Workers_Can_Build_Others_To_Take_Their_Place");
+/// ```
+#[derive(Clone, Copy)]
+pub struct AsAdaCase<T: AsRef<str>>(pub T);
+
+impl<T: AsRef<str>> AsAdaCase<T> {
+    /// Configure this conversion with the given [`Options`].
+    pub fn with_options(self, options: Options) -> WithOptions<Self> {
+        WithOptions {
+            case: self,
+            options,
+        }
+    }
+}
+
+impl<T: AsRef<str>> fmt::Display for AsAdaCase<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&AsAdaCase(self.0.as_ref()).with_options(Options::new()), f)
+    }
+}
+
+impl<T: AsRef<str>> From<AsAdaCase<T>> for String {
+    fn from(case: AsAdaCase<T>) -> String {
+        case.to_string()
+    }
+}
+
+impl<T: AsRef<str>> fmt::Display for WithOptions<'_, AsAdaCase<T>> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        transform(
+            self.case.0.as_ref(),
+            self.options,
+            |s, f| capitalize(s, self.options, f),
+            |_, f| write!(f, "{}", self.options.separator.unwrap_or('_')),
+            f,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ToAdaCase;
+
+    macro_rules! t {
+        ($t:ident : $s1:expr => $s2:expr) => {
+            #[test]
+            fn $t() {
+                assert_eq!($s1.to_ada_case(), $s2)
+            }
+        };
+    }
+
+    t!(test1: "CamelCase" => "Camel_Case");
+    t!(test2: "This is Human case." => "This_Is_Human_Case");
+    t!(test3: "MixedUP CamelCase, with some Spaces" => "Mixed_Up_Camel_Case_With_Some_Spaces");
+    t!(test4: "mixed_up_ snake_case with some _spaces" => "Mixed_Up_Snake_Case_With_Some_Spaces");
+    t!(test5: "kebab-case" => "Kebab_Case");
+    t!(test6: "SHOUTY_SNAKE_CASE" => "Shouty_Snake_Case");
+    t!(test7: "snake_case" => "Snake_Case");
+    t!(test8: "this-contains_ ALLKinds OfWord_Boundaries" => "This_Contains_All_Kinds_Of_Word_Boundaries");
+    t!(test9: "XΣXΣ baﬄe" => "Xσxς_Baﬄe");
+    t!(test10: "XMLHttpRequest" => "Xml_Http_Request");
+    t!(test11: "FIELD_NAME11" => "Field_Name11");
+    t!(test12: "99BOTTLES" => "99bottles");
+    t!(test13: "FieldNamE11" => "Field_Nam_E11");
+    t!(test14: "abc123def456" => "Abc123def456");
+    t!(test16: "abc123DEF456" => "Abc123_Def456");
+    t!(test17: "abc123Def456" => "Abc123_Def456");
+    t!(test18: "abc123DEf456" => "Abc123_D_Ef456");
+    t!(test19: "ABC123def456" => "Abc123def456");
+    t!(test20: "ABC123DEF456" => "Abc123def456");
+    t!(test21: "ABC123Def456" => "Abc123_Def456");
+    t!(test22: "ABC123DEf456" => "Abc123d_Ef456");
+    t!(test23: "ABC123dEEf456FOO" => "Abc123d_E_Ef456_Foo");
+    t!(test24: "abcDEF" => "Abc_Def");
+    t!(test25: "ABcDE" => "A_Bc_De");
+    t!(test26: "xml http request" => "Xml_Http_Request");
+}
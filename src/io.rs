@@ -0,0 +1,116 @@
+use core::fmt;
+use std::io;
+
+/// An adapter which writes formatted text to an [`io::Write`].
+///
+/// The case conversion wrappers implement [`fmt::Display`], and so can be
+/// written with `write!` to anything implementing [`fmt::Write`]. This adapter
+/// forwards what is written to an `io::Write`, such as a file or a socket,
+/// without collecting the output in a `String` first.
+///
+/// An `fmt::Write` can only return [`fmt::Error`], so the I/O error which
+/// caused a write to fail is kept, and returned by [`IoWriter::finish`].
+///
+/// ## Example:
+///
+/// ```
+/// use core::fmt::Write;
+/// use heck::{AsSnakeCase, IoWriter};
+///
+/// let mut out = Vec::new();
+/// let mut writer = IoWriter::new(&mut out);
+/// let _ = write!(writer, "{}", AsSnakeCase("HelloWorld"));
+/// writer.finish().unwrap();
+/// assert_eq!(out, b"hello_world");
+/// ```
+#[derive(Debug)]
+pub struct IoWriter<'a, W: io::Write + ?Sized> {
+    writer: &'a mut W,
+    result: io::Result<()>,
+}
+
+impl<'a, W: io::Write + ?Sized> IoWriter<'a, W> {
+    /// Create an adapter which writes to `writer`.
+    pub fn new(writer: &'a mut W) -> Self {
+        IoWriter {
+            writer,
+            result: Ok(()),
+        }
+    }
+
+    /// Return the error which made a write fail, if any.
+    ///
+    /// Once a write has failed nothing more is written, and this returns the
+    /// first error. If a `fmt::Display` implementation failed without an I/O
+    /// error, this returns `Ok(())`; that failure is the `fmt::Error` returned
+    /// by `write!`.
+    pub fn finish(self) -> io::Result<()> {
+        self.result
+    }
+}
+
+impl<W: io::Write + ?Sized> fmt::Write for IoWriter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if self.result.is_err() {
+            return Err(fmt::Error);
+        }
+        self.writer.write_all(s.as_bytes()).map_err(|error| {
+            self.result = Err(error);
+            fmt::Error
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::fmt::Write;
+    use std::{io, vec::Vec};
+
+    use super::IoWriter;
+    use crate::{AsKebabCase, AsSnakeCase};
+
+    #[test]
+    fn write() {
+        let mut out = Vec::new();
+        let mut writer = IoWriter::new(&mut out);
+        write!(
+            writer,
+            "{} {}",
+            AsSnakeCase("FooBar"),
+            AsKebabCase("Baz Qux")
+        )
+        .unwrap();
+        writer.finish().unwrap();
+        assert_eq!(out, b"foo_bar baz-qux");
+    }
+
+    struct Failing {
+        written: usize,
+    }
+
+    impl io::Write for Failing {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if self.written + buf.len() > 4 {
+                Err(io::Error::new(io::ErrorKind::WriteZero, "full"))
+            } else {
+                self.written += buf.len();
+                Ok(buf.len())
+            }
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn error() {
+        let mut out = Failing { written: 0 };
+        let mut writer = IoWriter::new(&mut out);
+        assert!(write!(writer, "{}", AsSnakeCase("FooBarBaz")).is_err());
+        assert!(writer.write_str("more").is_err());
+        let error = writer.finish().unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::WriteZero);
+        assert_eq!(out.written, 4);
+    }
+}
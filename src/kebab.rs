@@ -1,8 +1,11 @@
 use core::fmt;
 
-use alloc::{borrow::ToOwned, string::ToString};
+use alloc::{
+    borrow::ToOwned,
+    string::{String, ToString},
+};
 
-use crate::{lowercase, transform};
+use crate::{lowercase, transform, Options, WithOptions};
 
 /// This trait defines a kebab case conversion.
 ///
@@ -37,23 +40,147 @@ impl ToKebabCase for str {
 /// let sentence = "We are going to inherit the earth.";
 /// assert_eq!(format!("{}", AsKebabCase(sentence)), "we-are-going-to-inherit-the-earth");
 /// ```
+#[derive(Clone, Copy)]
 pub struct AsKebabCase<T: AsRef<str>>(pub T);
 
+impl<T: AsRef<str>> AsKebabCase<T> {
+    /// Configure this conversion with the given [`Options`].
+    pub fn with_options(self, options: Options) -> WithOptions<Self> {
+        WithOptions {
+            case: self,
+            options,
+        }
+    }
+}
+
 impl<T: AsRef<str>> fmt::Display for AsKebabCase<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        transform(self.0.as_ref(), lowercase, |f| write!(f, "-"), f)
+        fmt::Display::fmt(
+            &AsKebabCase(self.0.as_ref()).with_options(Options::new()),
+            f,
+        )
+    }
+}
+
+impl<T: AsRef<str>> From<AsKebabCase<T>> for String {
+    fn from(case: AsKebabCase<T>) -> String {
+        case.to_string()
+    }
+}
+
+impl<T: AsRef<str>> fmt::Display for WithOptions<'_, AsKebabCase<T>> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        transform(
+            self.case.0.as_ref(),
+            self.options,
+            |s, f| lowercase(s, self.options, f),
+            |_, f| write!(f, "{}", self.options.separator.unwrap_or('-')),
+            f,
+        )
+    }
+}
+
+/// Convert a string to a kebab case slug of at most `max_words` words.
+///
+/// Words after the first `max_words` are dropped. This is the same as kebab
+/// case with [`Options::max_words`].
+///
+/// ## Example:
+///
+/// ```
+/// use heck::to_slug;
+///
+/// assert_eq!(to_slug("A Very Long Title Here", 3), "a-very-long");
+/// assert_eq!(to_slug("Short Title", 3), "short-title");
+/// ```
+pub fn to_slug(s: &str, max_words: usize) -> String {
+    AsKebabCase(s)
+        .with_options(Options::new().max_words(max_words))
+        .to_string()
+}
+
+/// The longest label allowed by RFC 1123.
+const MAX_DNS_LABEL_LEN: usize = 63;
+
+/// Convert a string to kebab case and check that it is a valid DNS label.
+///
+/// A valid label, as defined by RFC 1123 and used for names in Kubernetes and
+/// many cloud services, contains only lowercase ASCII letters, digits and
+/// hyphens, does not begin or end with a hyphen, and is at most 63 characters
+/// long. Labels which are too long are truncated, dropping any hyphens left at
+/// the end.
+///
+/// ## Example:
+///
+/// ```
+/// use heck::{to_dns_label, DnsLabelError};
+///
+/// assert_eq!(to_dns_label("MyService v2").unwrap(), "my-service-v2");
+/// assert_eq!(to_dns_label("Café"), Err(DnsLabelError::InvalidChar('é')));
+/// assert_eq!(to_dns_label("--"), Err(DnsLabelError::Empty));
+/// ```
+pub fn to_dns_label(s: &str) -> Result<String, DnsLabelError> {
+    let mut label = s.to_kebab_case();
+
+    if let Some(c) = label
+        .chars()
+        .find(|&c| !c.is_ascii_alphanumeric() && c != '-')
+    {
+        return Err(DnsLabelError::InvalidChar(c));
+    }
+
+    // The label is ASCII, so it can be truncated at any byte.
+    label.truncate(MAX_DNS_LABEL_LEN);
+    let len = label.trim_end_matches('-').len();
+    label.truncate(len);
+
+    if label.is_empty() {
+        Err(DnsLabelError::Empty)
+    } else {
+        Ok(label)
+    }
+}
+
+/// The error returned by [`to_dns_label`] when a string cannot be converted to
+/// a DNS label.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DnsLabelError {
+    /// The string contains no words.
+    Empty,
+    /// The kebab case string contains a character which is not allowed in a
+    /// DNS label.
+    InvalidChar(char),
+}
+
+impl fmt::Display for DnsLabelError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DnsLabelError::Empty => write!(f, "DNS label is empty"),
+            DnsLabelError::InvalidChar(c) => {
+                write!(f, "invalid character {:?} in DNS label", c)
+            }
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::ToKebabCase;
+    use super::{to_dns_label, to_slug, AsKebabCase, DnsLabelError, ToKebabCase};
+    use crate::{AsTitleCase, Options};
+    use alloc::{format, string::ToString};
 
     macro_rules! t {
-        ($t:ident : $s1:expr => $s2:expr) => {
+        ($t:ident : $($s1:expr => $s2:expr),+ $(,)?) => {
             #[test]
             fn $t() {
-                assert_eq!($s1.to_kebab_case(), $s2)
+                $(assert_eq!($s1.to_kebab_case(), $s2);)+
+            }
+        };
+        ($t:ident : $options:expr ; $($s1:expr => $s2:expr),+ $(,)?) => {
+            #[test]
+            fn $t() {
+                let options = $options;
+                $(assert_eq!(AsKebabCase($s1).with_options(options).to_string(), $s2);)+
             }
         };
     }
@@ -72,4 +199,84 @@ mod tests {
     // Japanese and Chinese do not have word separation.
     t!(test12: "ファイルを読み込み" => "ファイルを読み込み");
     t!(test13: "祝你一天过得愉快" => "祝你一天过得愉快");
+    t!(test14: "کتاب\u{200C}ها" => "کتاب\u{200C}ها");
+    t!(test15: "نامه\u{200C}های_من" => "نامه\u{200C}های-من");
+
+    #[test]
+    fn dns_label() {
+        assert_eq!(to_dns_label("XMLHttpRequest").unwrap(), "xml-http-request");
+        assert_eq!(
+            to_dns_label("-leading and trailing-").unwrap(),
+            "leading-and-trailing"
+        );
+        assert_eq!(to_dns_label("Pod 42").unwrap(), "pod-42");
+    }
+
+    #[test]
+    fn dns_label_too_long() {
+        let label = to_dns_label(&"word ".repeat(20)).unwrap();
+        assert_eq!(label.len(), 63);
+        assert!(label.ends_with("-word-wor"));
+
+        let long = format!("{} b", "a".repeat(62));
+        assert_eq!(to_dns_label(&long).unwrap(), "a".repeat(62));
+    }
+
+    #[test]
+    fn dns_label_invalid() {
+        assert_eq!(to_dns_label(""), Err(DnsLabelError::Empty));
+        assert_eq!(to_dns_label("__"), Err(DnsLabelError::Empty));
+        assert_eq!(to_dns_label("naïve"), Err(DnsLabelError::InvalidChar('ï')));
+        assert_eq!(
+            to_dns_label("ファイル"),
+            Err(DnsLabelError::InvalidChar('フ'))
+        );
+    }
+
+    t!(separator: Options::new().separator('/');
+        "XMLHttpRequest" => "xml/http/request",
+        "a--b__c" => "a/b/c",
+        "single" => "single",
+    );
+
+    #[test]
+    fn slug() {
+        assert_eq!(to_slug("a very long title here", 3), "a-very-long");
+        assert_eq!(to_slug("--a__very  long", 2), "a-very");
+        assert_eq!(to_slug("XMLHttpRequest", 2), "xml-http");
+        assert_eq!(
+            to_slug("a very long title here", 5),
+            "a-very-long-title-here"
+        );
+        assert_eq!(
+            to_slug("a very long title here", 10),
+            "a-very-long-title-here"
+        );
+        assert_eq!(to_slug("a very long title here", 1), "a");
+        assert_eq!(to_slug("a very long title here", 0), "");
+        assert_eq!(to_slug("", 3), "");
+    }
+
+    #[test]
+    fn force_lowercase() {
+        let map = [("ios", "iOS"), ("http", "HTTP")];
+        let options = Options::new().acronym_map(&map);
+        let convert = |s: &str, options: Options| AsKebabCase(s).with_options(options).to_string();
+
+        assert_eq!(convert("iosHttpClient", options), "iOS-HTTP-client");
+        assert_eq!(
+            convert("iosHttpClient", options.force_lowercase(true)),
+            "ios-http-client"
+        );
+        assert_eq!(
+            convert("ΟΔΟΣ_ΣΑΣ", Options::new().force_lowercase(true)),
+            "οδος-σας"
+        );
+        assert_eq!(
+            AsTitleCase("the NASA launch")
+                .with_options(Options::new().keep_all_caps(true).force_lowercase(true))
+                .to_string(),
+            "the nasa launch"
+        );
+    }
 }
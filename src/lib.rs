@@ -10,21 +10,53 @@
 //! within those words in this manner:
 //!
 //! 1. If an uppercase character is followed by lowercase letters, a word
-//! boundary is considered to be just prior to that uppercase character.
+//!    boundary is considered to be just prior to that uppercase character.
 //! 2. If multiple uppercase characters are consecutive, they are considered to
-//! be within a single word, except that the last will be part of the next word
-//! if it is followed by lowercase characters (see rule 1).
+//!    be within a single word, except that the last will be part of the next
+//!    word if it is followed by lowercase characters (see rule 1).
 //!
 //! That is, "HelloWorld" is segmented `Hello|World` whereas "XMLHttpRequest" is
 //! segmented `XML|Http|Request`.
 //!
+//! Digits are not cased, so they never begin a word of their own and stay with
+//! the characters before them: "HTTP2Server" is segmented `HTTP2|Server`.
+//! Likewise, letters which have no other case (such as `ĸ`) are treated as
+//! uncased, and combining diacritical marks are part of the word they follow. A
+//! zero width non-joiner between two letters, as used in Persian, is also part of
+//! the word.
+//!
+//! Which characters are letters and digits is decided by the version of Unicode
+//! supported by the Rust standard library. Code points which are unassigned in
+//! that version are separators, so text using newly assigned letters may be
+//! segmented differently by later Rust versions, but text using only assigned
+//! characters will not be.
+//!
 //! Characters not within words (such as spaces, punctuations, and underscores)
 //! are not included in the output string except as they are a part of the case
 //! being converted to. Multiple adjacent word boundaries (such as a series of
 //! underscores) are folded into one. ("hello__world" in snake case is therefore
 //! "hello_world", not the exact same string). Leading or trailing word boundary
 //! indicators are dropped, except insofar as CamelCase capitalizes the first
-//! word.
+//! word. Which separator characters are used makes no difference to the words
+//! found, unless an option such as `keep_middle_dot` keeps some of them.
+//!
+//! Cases which capitalize words do so by uppercasing the first character of
+//! the word and lowercasing the rest. A word which begins with a digit, such as
+//! "123abc" or "1st", is therefore not capitalized at all.
+//!
+//! ### Round trips
+//!
+//! Converting to a case and back does not always give back the original string,
+//! because the conversion loses information. The most common losses are:
+//!
+//! - Acronyms are capitalized like other words, so "XMLHttpRequest" becomes
+//!   "xml_http_request" and then "XmlHttpRequest".
+//! - In the camel cases, two adjacent words with a single letter each become one
+//!   run of capitals, so "a_b_c" becomes "ABC" and then "abc".
+//! - A word beginning with a digit joins the word before it in the camel cases,
+//!   so "foo_2" becomes "Foo2" and then "foo2".
+//! - Case mappings which change the length of a word are not reversed, so
+//!   "straße" becomes "STRASSE" in shouty snake case, and then "strasse".
 //!
 //! ### Cases contained in this library:
 //!
@@ -36,14 +68,24 @@
 //! 6. Title Case
 //! 7. SHOUTY-KEBAB-CASE
 //! 8. Train-Case
+//! 9. Ada_Case
 #![deny(missing_docs)]
 #![forbid(unsafe_code)]
 #![no_std]
 
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
+mod ada;
+#[cfg(feature = "std")]
+mod io;
 mod kebab;
 mod lower_camel;
+mod options;
+#[cfg(all(feature = "std", unix))]
+mod os_str;
+mod path;
 mod shouty_kebab;
 mod shouty_snake;
 mod snake;
@@ -51,30 +93,764 @@ mod title;
 mod train;
 mod upper_camel;
 
-pub use kebab::{AsKebabCase, ToKebabCase};
-pub use lower_camel::{AsLowerCamelCase, ToLowerCamelCase};
+pub use ada::{AsAdaCase, ToAdaCase};
+#[cfg(feature = "std")]
+pub use io::IoWriter;
+pub use kebab::{to_dns_label, to_slug, AsKebabCase, DnsLabelError, ToKebabCase};
+pub use lower_camel::{to_smart_camel_case, AsLowerCamelCase, ToLowerCamelCase};
+pub use options::{ContractionPolicy, Locale, MathSymbols, Options, WithOptions};
+#[cfg(all(feature = "std", unix))]
+pub use os_str::{
+    to_kebab_case_os_lossy, to_snake_case_os_lossy, try_to_kebab_case_os, try_to_snake_case_os,
+};
+pub use path::convert_path;
 pub use shouty_kebab::{AsShoutyKebabCase, ToShoutyKebabCase};
 pub use shouty_snake::{
     AsShoutySnakeCase, AsShoutySnakeCase as AsShoutySnekCase, ToShoutySnakeCase, ToShoutySnekCase,
 };
-pub use snake::{AsSnakeCase, AsSnakeCase as AsSnekCase, ToSnakeCase, ToSnekCase};
-pub use title::{AsTitleCase, ToTitleCase};
+pub use snake::{
+    to_snake_case_lossy, to_snake_case_strict, AsSnakeCase, AsSnakeCase as AsSnekCase, InvalidChar,
+    SnakeCaseStream, SnakeCased, ToSnakeCase, ToSnekCase,
+};
+pub use title::{to_spaced_case_with, AsTitleCase, ToTitleCase};
 pub use train::{AsTrainCase, ToTrainCase};
 pub use upper_camel::{
     AsUpperCamelCase, AsUpperCamelCase as AsPascalCase, ToPascalCase, ToUpperCamelCase,
 };
 
-use core::fmt;
+use core::{
+    convert::Infallible,
+    fmt::{self, Write},
+};
+
+use alloc::{collections::VecDeque, string::String, vec::Vec};
+
+/// Fill `buf` with the byte ranges of the words in `s`.
+///
+/// The buffer is cleared first, so that a single allocation can be reused to
+/// segment many strings. Each range is a `(start, end)` pair such that
+/// `&s[start..end]` is a word, exactly as it is found by the case conversions.
+///
+/// ## Example:
+///
+/// ```
+/// let mut buf = Vec::new();
+///
+/// heck::boundaries_into("XMLHttpRequest", &mut buf);
+/// assert_eq!(buf, [(0, 3), (3, 7), (7, 14)]);
+///
+/// heck::boundaries_into("hello, world", &mut buf);
+/// assert_eq!(buf, [(0, 5), (7, 12)]);
+/// ```
+pub fn boundaries_into(s: &str, buf: &mut Vec<(usize, usize)>) {
+    buf.clear();
+    each_word(s, Options::new(), |start, end| buf.push((start, end)));
+}
+
+/// Split `s` into words, returning each word as an owned string.
+///
+/// The words are the same as those found by the case conversions, copied from
+/// `s` without changing their case. Separators are not included.
+///
+/// ## Example:
+///
+/// ```
+/// assert_eq!(heck::decompose("XMLHttpRequest"), ["XML", "Http", "Request"]);
+/// assert_eq!(heck::decompose("_a__b_"), ["a", "b"]);
+/// assert!(heck::decompose("").is_empty());
+/// ```
+pub fn decompose(s: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    each_word(s, Options::new(), |start, end| {
+        words.push(s[start..end].into())
+    });
+    words
+}
+
+/// Split `s` into words, and write them to `out` with `sep` between them.
+///
+/// Each word is written by `render_word`, which is given the word as it is in
+/// `s`, whether it is the first word, and `out`. The words are the same as
+/// those found by the case conversions, so each of the built-in cases is a
+/// separator and a way of rendering words; this lets a consumer use the same
+/// segmentation with its own rendering.
+///
+/// ## Example:
+///
+/// ```
+/// use core::fmt::Write;
+///
+/// let mut snake = String::new();
+/// heck::segment_and_render(
+///     "XMLHttpRequest",
+///     "_",
+///     |word, _, out: &mut String| {
+///         out.extend(word.chars().flat_map(char::to_lowercase));
+///         Ok(())
+///     },
+///     &mut snake,
+/// )
+/// .unwrap();
+/// assert_eq!(snake, "xml_http_request");
+///
+/// let mut camel = String::new();
+/// heck::segment_and_render(
+///     "foo_bar_baz",
+///     "",
+///     |word, first, out: &mut String| {
+///         if first {
+///             out.write_str(word)
+///         } else {
+///             let mut chars = word.chars();
+///             out.extend(chars.next().map(|c| c.to_ascii_uppercase()));
+///             out.write_str(chars.as_str())
+///         }
+///     },
+///     &mut camel,
+/// )
+/// .unwrap();
+/// assert_eq!(camel, "fooBarBaz");
+/// ```
+pub fn segment_and_render<S, R>(s: &str, sep: &str, mut render_word: R, out: &mut S) -> fmt::Result
+where
+    S: fmt::Write,
+    R: FnMut(&str, bool, &mut S) -> fmt::Result,
+{
+    let mut first = true;
+    segment(s, Options::new(), |start, end| {
+        if !first {
+            out.write_str(sep)?;
+        }
+        render_word(&s[start..end], first, out)?;
+        first = false;
+        Ok(())
+    })
+}
+
+/// Split `s` into words, and classify each word by its case.
+///
+/// The words are the same as those found by the case conversions. This lets a
+/// consumer render words itself, for example to keep acronyms in uppercase.
+///
+/// ## Example:
+///
+/// ```
+/// use heck::{classified_words, WordKind};
+///
+/// assert_eq!(
+///     classified_words("XMLHttpRequest2 v2 42"),
+///     [
+///         ("XML", WordKind::Acronym),
+///         ("Http", WordKind::Capitalized),
+///         ("Request2", WordKind::Capitalized),
+///         ("v2", WordKind::Lowercase),
+///         ("42", WordKind::Numeric),
+///     ]
+/// );
+/// ```
+pub fn classified_words(s: &str) -> Vec<(&str, WordKind)> {
+    let mut words = Vec::new();
+    each_word(s, Options::new(), |start, end| {
+        let word = &s[start..end];
+        words.push((word, WordKind::of(word)));
+    });
+    words
+}
+
+/// The kind of a word, as returned by [`classified_words`].
+///
+/// Words are classified by their cased characters, which are the letters that
+/// have both an uppercase and a lowercase form; digits and other characters in
+/// a word which has cased characters are ignored.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum WordKind {
+    /// Every cased character is lowercase, like `http` or `v2`.
+    Lowercase,
+    /// The first cased character is uppercase and the rest are lowercase, like
+    /// `Http`, `Request2` or `A`.
+    Capitalized,
+    /// There are at least two cased characters and all of them are uppercase,
+    /// like `XML`. Such words are most likely acronyms.
+    Acronym,
+    /// The cased characters are in some other mix of cases, like `ABC123d`.
+    /// Digits between the changes of case keep such words together.
+    Mixed,
+    /// The word has no cased characters and all of its characters are numeric,
+    /// like `42`.
+    Numeric,
+    /// The word has no cased characters, like `ファイル`.
+    Uncased,
+}
+
+impl WordKind {
+    fn of(word: &str) -> WordKind {
+        let mut cased = word
+            .chars()
+            .filter(|&c| changes_to_lowercase(c) || changes_to_uppercase(c));
+
+        match cased.next() {
+            None if word.chars().all(char::is_numeric) => WordKind::Numeric,
+            None => WordKind::Uncased,
+            Some(first) if changes_to_uppercase(first) => {
+                if cased.all(changes_to_uppercase) {
+                    WordKind::Lowercase
+                } else {
+                    WordKind::Mixed
+                }
+            }
+            Some(_) => {
+                let (mut upper, mut lower) = (false, false);
+                for c in cased {
+                    upper |= changes_to_lowercase(c);
+                    lower |= changes_to_uppercase(c);
+                }
+                match (upper, lower) {
+                    (_, false) if upper => WordKind::Acronym,
+                    (false, _) => WordKind::Capitalized,
+                    _ => WordKind::Mixed,
+                }
+            }
+        }
+    }
+}
+
+/// Call `word` with the byte range of each word in `s` in order.
+fn each_word<W>(s: &str, options: Options, mut word: W)
+where
+    W: FnMut(usize, usize),
+{
+    let result: Result<(), Infallible> = segment(s, options, |start, end| {
+        word(start, end);
+        Ok(())
+    });
+    match result {
+        Ok(()) => {}
+        Err(never) => match never {},
+    }
+}
 
 fn transform<F, G>(
     s: &str,
+    options: Options,
+    with_word: F,
+    boundary: G,
+    f: &mut fmt::Formatter,
+) -> fmt::Result
+where
+    F: FnMut(&str, &mut fmt::Formatter) -> fmt::Result,
+    G: FnMut(&str, &mut fmt::Formatter) -> fmt::Result,
+{
+    transform_words(s, options, false, with_word, boundary, f)
+}
+
+/// Like `transform`, where `lower_camel` is whether the case is
+/// lowerCamelCase, whose first word is lowercased even if it is an acronym
+/// with a given spelling, unless `Options::lowercase_leading_acronym` is off.
+fn transform_words<F, G>(
+    s: &str,
+    options: Options,
+    lower_camel: bool,
     mut with_word: F,
     mut boundary: G,
     f: &mut fmt::Formatter,
 ) -> fmt::Result
 where
     F: FnMut(&str, &mut fmt::Formatter) -> fmt::Result,
-    G: FnMut(&mut fmt::Formatter) -> fmt::Result,
+    G: FnMut(&str, &mut fmt::Formatter) -> fmt::Result,
+{
+    let s = s.strip_prefix(options.strip_prefix).unwrap_or(s);
+    let s = s.strip_suffix(options.strip_suffix).unwrap_or(s);
+
+    let s = if options.keep_leading_underscore {
+        let rest = s.trim_start_matches('_');
+        // Unless `Options::max_words` drops every word, the first word is
+        // always written, so the underscores can be written before it now.
+        if options.max_words != Some(0) {
+            f.write_str(&s[..s.len() - rest.len()])?;
+        }
+        rest
+    } else {
+        s
+    };
+
+    #[cfg(feature = "unicode-normalization")]
+    let folded;
+    #[cfg(feature = "unicode-normalization")]
+    let s = if options.ascii_fold {
+        folded = ascii_fold(s);
+        &folded[..]
+    } else {
+        s
+    };
+
+    let (s, extension) = if options.preserve_extension {
+        split_extension(s)
+    } else {
+        (s, None)
+    };
+
+    let mut first_word = true;
+    // The end of the previous word, so that the boundary can be given the
+    // separator characters which preceded the current word.
+    let mut last_end = 0;
+
+    // The number of words written, for `Options::max_words`.
+    let mut written = 0;
+    let mut write_word = |start: usize, end: usize| {
+        if options.max_words.map_or(false, |max| written >= max) {
+            return Ok(());
+        }
+        written += 1;
+
+        if first_word {
+            first_word = false;
+        } else {
+            boundary(&s[last_end..start], f)?;
+        }
+        last_end = end;
+
+        let dropped: String;
+        let mut word = &s[start..end];
+        if options.contractions == ContractionPolicy::Drop && word.contains(is_apostrophe) {
+            dropped = word.chars().filter(|&c| !is_apostrophe(c)).collect();
+            word = &dropped;
+        }
+        if options.force_lowercase {
+            return lowercase(word, options, f);
+        }
+        match options.spelling(word) {
+            // An acronym beginning lowerCamelCase is lowercased like any
+            // other first word, but a brand like "iOS" is kept.
+            Some(spelling)
+                if written == 1
+                    && lower_camel
+                    && options.lowercase_leading_acronym
+                    && !spelling.chars().any(char::is_lowercase) =>
+            {
+                lowercase(spelling, options, f)
+            }
+            Some(spelling) => f.write_str(spelling),
+            None => with_word(word, f),
+        }
+    };
+
+    if !options.joins_words() {
+        segment(s, options, &mut write_word)?;
+    } else {
+        // A word with a given spelling may have been split at changes of
+        // case, as "PostgreSQL" is split into "Postgre" and "SQL", and so may
+        // a protocol token. Words with no separator between them are joined,
+        // as many as possible, if together they have a given spelling or are
+        // a protocol token. Only as many words are kept as could be joined, so
+        // that long runs of words take linear time and bounded memory.
+        let max_joined = options.max_joined_words().max(1);
+        let mut pending = VecDeque::with_capacity(max_joined);
+
+        segment(s, options, |start, end| {
+            if pending
+                .back()
+                .map_or(false, |&(_, last_end)| last_end != start)
+            {
+                while !pending.is_empty() {
+                    write_joined(s, options, &mut pending, &mut write_word)?;
+                }
+            }
+            pending.push_back((start, end));
+            if pending.len() == max_joined {
+                write_joined(s, options, &mut pending, &mut write_word)?;
+            }
+            Ok(())
+        })?;
+
+        while !pending.is_empty() {
+            write_joined(s, options, &mut pending, &mut write_word)?;
+        }
+    }
+
+    if options.trailing_separator && !first_word {
+        boundary("", f)?;
+    }
+
+    // The extension belongs to the words before it, and is dropped with them.
+    match extension {
+        Some(extension) if !first_word => {
+            write!(f, ".")?;
+            lowercase(extension, options, f)?;
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Write the first of the `pending` words with no separators between them,
+/// joined with as many of the words after it as possible, and remove the words
+/// written from `pending`.
+fn write_joined<W>(
+    s: &str,
+    options: Options,
+    pending: &mut VecDeque<(usize, usize)>,
+    write_word: &mut W,
+) -> fmt::Result
+where
+    W: FnMut(usize, usize) -> fmt::Result,
+{
+    let start = pending[0].0;
+    let joined = (1..pending.len())
+        .rev()
+        .find(|&j| options.is_joined_word(&s[start..pending[j].1]))
+        .unwrap_or(0);
+    let end = pending[joined].1;
+    pending.drain(..=joined);
+    write_word(start, end)
+}
+
+/// Split a file extension off the end of `s`.
+///
+/// An extension is the text after the last `.`, if it is one to five ASCII
+/// letters and digits including at least one letter, and the text before the
+/// `.` is not empty.
+fn split_extension(s: &str) -> (&str, Option<&str>) {
+    match s.rsplit_once('.') {
+        Some((stem, extension))
+            if !stem.is_empty()
+                && (1..=5).contains(&extension.len())
+                && extension.bytes().all(|b| b.is_ascii_alphanumeric())
+                && extension.bytes().any(|b| b.is_ascii_alphabetic()) =>
+        {
+            (stem, Some(extension))
+        }
+        _ => (s, None),
+    }
+}
+
+/// Split `s` into words, calling `word` with the byte range of each word in
+/// order.
+fn segment<E, W>(s: &str, options: Options, mut word: W) -> Result<(), E>
+where
+    W: FnMut(usize, usize) -> Result<(), E>,
+{
+    // The start of the current run of word characters.
+    let mut chunk_start = None;
+    // The end of the last emoji sequence, whose characters have been written.
+    let mut emoji_end = 0;
+
+    for (i, c) in s.char_indices() {
+        if i < emoji_end {
+            continue;
+        }
+
+        if options.emoji_words && is_emoji(c) {
+            if let Some(start) = chunk_start.take() {
+                segment_run(s, start, i, options, &mut word)?;
+            }
+            emoji_end = emoji_sequence_end(s, i);
+            word(i, emoji_end)?;
+            continue;
+        }
+
+        let in_word =
+            is_word_char(s, i, c, options) || chunk_start.is_some() && is_combining_diacritic(c);
+        match (chunk_start, in_word) {
+            (None, true) => chunk_start = Some(i),
+            (Some(start), false) => {
+                segment_run(s, start, i, options, &mut word)?;
+                chunk_start = None;
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(start) = chunk_start {
+        segment_run(s, start, s.len(), options, &mut word)?;
+    }
+
+    Ok(())
+}
+
+/// Whether `c` is an emoji, for `Options::emoji_words`.
+///
+/// This covers the blocks which contain most emoji, rather than the exact
+/// `Extended_Pictographic` property, and leaves out characters which are also
+/// letters or digits.
+fn is_emoji(c: char) -> bool {
+    match c {
+        // Miscellaneous Symbols and Dingbats
+        '\u{2600}'..='\u{27BF}'
+        // Watch, hourglass and media controls
+        | '\u{231A}'..='\u{231B}'
+        | '\u{23E9}'..='\u{23FA}'
+        // Stars, circles and squares
+        | '\u{2B1B}'..='\u{2B1C}'
+        | '\u{2B50}'
+        | '\u{2B55}'
+        // Mahjong and playing cards
+        | '\u{1F000}'..='\u{1F0FF}'
+        // Regional indicators, which are paired into flags
+        | '\u{1F1E6}'..='\u{1F1FF}'
+        // Enclosed ideographs, pictographs, emoticons, transport and map
+        // symbols, geometric shapes and the supplemental blocks
+        | '\u{1F200}'..='\u{1F6FF}'
+        | '\u{1F7E0}'..='\u{1F7EB}'
+        | '\u{1F900}'..='\u{1F9FF}'
+        | '\u{1FA70}'..='\u{1FAFF}' => !c.is_alphanumeric(),
+        _ => false,
+    }
+}
+
+/// The end of the emoji sequence which begins with the emoji at byte index
+/// `start` of `s`.
+///
+/// The sequence continues through variation selectors, skin tone modifiers,
+/// keycaps and tags, through a zero width joiner followed by another emoji,
+/// and from one regional indicator to the next, so that each is one word.
+fn emoji_sequence_end(s: &str, start: usize) -> usize {
+    let is_regional_indicator = |c| ('\u{1F1E6}'..='\u{1F1FF}').contains(&c);
+
+    let mut chars = s[start..].char_indices().peekable();
+    let (_, first) = chars.next().unwrap();
+    let mut end = start + first.len_utf8();
+
+    if is_regional_indicator(first) {
+        if let Some(&(i, c)) = chars.peek() {
+            if is_regional_indicator(c) {
+                return start + i + c.len_utf8();
+            }
+        }
+        return end;
+    }
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\u{FE0F}' | '\u{1F3FB}'..='\u{1F3FF}' | '\u{20E3}' | '\u{E0020}'..='\u{E007F}' => {
+                end = start + i + c.len_utf8();
+            }
+            '\u{200D}' => match chars.peek() {
+                Some(&(j, next)) if is_emoji(next) => {
+                    chars.next();
+                    end = start + j + next.len_utf8();
+                }
+                _ => break,
+            },
+            _ => break,
+        }
+    }
+
+    end
+}
+
+/// Split the run of word characters `s[start..end]` into words, calling `word`
+/// with the byte range of each word in `s`.
+fn segment_run<E, W>(
+    s: &str,
+    start: usize,
+    end: usize,
+    options: Options,
+    word: &mut W,
+) -> Result<(), E>
+where
+    W: FnMut(usize, usize) -> Result<(), E>,
+{
+    if !options.merge_single_letter_words {
+        return segment_scripts(s, start, end, options, word);
+    }
+
+    // The start of a single letter word, which is joined to the word after it.
+    let mut pending = None;
+
+    segment_scripts(s, start, end, options, &mut |word_start, word_end| {
+        if let Some(pending_start) = pending.take() {
+            return word(pending_start, word_end);
+        }
+
+        let mut chars = s[word_start..word_end].chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) if c.is_alphabetic() => {
+                pending = Some(word_start);
+                Ok(())
+            }
+            _ => word(word_start, word_end),
+        }
+    })?;
+
+    // A single letter at the end of the run has no word to join.
+    match pending {
+        Some(pending_start) => word(pending_start, end),
+        None => Ok(()),
+    }
+}
+
+/// Split the run of word characters `s[start..end]` at changes of script
+/// family if `Options::script_boundary` is set, and then into words.
+fn segment_scripts<E, W>(
+    s: &str,
+    start: usize,
+    end: usize,
+    options: Options,
+    word: &mut W,
+) -> Result<(), E>
+where
+    W: FnMut(usize, usize) -> Result<(), E>,
+{
+    if !options.script_boundary {
+        return segment_chunk(s, start, end, options, word);
+    }
+
+    // Split the run where the script family changes. Characters without a
+    // family, such as digits, stay with the characters before them.
+    let mut chunk_start = start;
+    let mut family = None;
+
+    for (i, c) in s[start..end].char_indices() {
+        match (family, ScriptFamily::of(c)) {
+            (Some(prev), Some(next)) if prev != next => {
+                segment_chunk(s, chunk_start, start + i, options, word)?;
+                chunk_start = start + i;
+                family = Some(next);
+            }
+            (None, Some(next)) => family = Some(next),
+            _ => {}
+        }
+    }
+
+    segment_chunk(s, chunk_start, end, options, word)
+}
+
+/// The families of scripts between which `Options::script_boundary` puts a
+/// word boundary.
+#[derive(Clone, Copy, PartialEq)]
+enum ScriptFamily {
+    /// Latin, Greek and Cyrillic, which are written with spaces and have case.
+    Alphabetic,
+    /// Han, Kana and Hangul, which are written without spaces between words.
+    Cjk,
+}
+
+impl ScriptFamily {
+    fn of(c: char) -> Option<ScriptFamily> {
+        match c {
+            // Hangul Jamo
+            '\u{1100}'..='\u{11FF}'
+            // CJK Radicals, Kangxi Radicals, CJK Symbols and Punctuation,
+            // Hiragana, Katakana, Bopomofo, Hangul Compatibility Jamo, ...,
+            // up to CJK Unified Ideographs Extension A and the CJK Unified
+            // Ideographs
+            | '\u{2E80}'..='\u{9FFF}'
+            // Hangul Jamo Extended-A
+            | '\u{A960}'..='\u{A97F}'
+            // Hangul Syllables and Hangul Jamo Extended-B
+            | '\u{AC00}'..='\u{D7FF}'
+            // CJK Compatibility Ideographs
+            | '\u{F900}'..='\u{FAFF}'
+            // Halfwidth Katakana and Hangul
+            | '\u{FF66}'..='\u{FFDC}'
+            // Kana Supplement and Extended-A
+            | '\u{1B000}'..='\u{1B16F}'
+            // The supplementary ideographic planes
+            | '\u{20000}'..='\u{3FFFF}' => Some(ScriptFamily::Cjk),
+            // Latin, Greek and Cyrillic, with their extensions
+            'A'..='Z'
+            | 'a'..='z'
+            | '\u{00C0}'..='\u{052F}'
+            | '\u{1C80}'..='\u{1C8F}'
+            | '\u{1D00}'..='\u{1FFF}'
+            | '\u{2C60}'..='\u{2C7F}'
+            | '\u{2DE0}'..='\u{2DFF}'
+            | '\u{A640}'..='\u{A69F}'
+            | '\u{A720}'..='\u{A7FF}'
+            | '\u{AB30}'..='\u{AB6F}'
+            | '\u{FF21}'..='\u{FF3A}'
+            | '\u{FF41}'..='\u{FF5A}' => Some(ScriptFamily::Alphabetic),
+            _ => None,
+        }
+    }
+}
+
+/// Whether `c` is a superscript or subscript digit, the only alphanumeric
+/// characters with the `ID_Compat_Math_Continue` property.
+fn is_math_digit(c: char) -> bool {
+    matches!(
+        c,
+        '\u{B2}' | '\u{B3}' | '\u{B9}' | '\u{2070}' | '\u{2074}'..='\u{2079}' | '\u{2080}'..='\u{2089}'
+    )
+}
+
+/// Whether `c` is an apostrophe which may join the parts of a contraction.
+fn is_apostrophe(c: char) -> bool {
+    c == '\'' || c == '\u{2019}'
+}
+
+/// Whether the character `c` at byte index `i` of `s` belongs to a word.
+fn is_word_char(s: &str, i: usize, c: char, options: Options) -> bool {
+    if options.math_symbols == MathSymbols::Separate && is_math_digit(c) {
+        return false;
+    }
+
+    if c.is_alphanumeric() {
+        return true;
+    }
+
+    // Some characters join the characters on either side of them into one
+    // word. A zero width non-joiner between two letters is part of the
+    // spelling of words in Persian and other languages.
+    let joins: Option<fn(char) -> bool> = match c {
+        '\u{200C}' => Some(char::is_alphabetic),
+        c if options.contractions != ContractionPolicy::Split && is_apostrophe(c) => {
+            Some(char::is_alphabetic)
+        }
+        '·' if options.keep_middle_dot => Some(char::is_alphanumeric),
+        '.' if options.keep_dotted_numbers => Some(|c| c.is_ascii_digit()),
+        _ => None,
+    };
+    joins.map_or(false, |joins| is_between(s, i, c, joins))
+}
+
+/// Whether the character `c` at byte index `i` of `s` has a character on
+/// each side for which `f` holds.
+fn is_between(s: &str, i: usize, c: char, f: fn(char) -> bool) -> bool {
+    let before = s[..i].chars().next_back();
+    let after = s[i + c.len_utf8()..].chars().next();
+    before.map_or(false, f) && after.map_or(false, f)
+}
+
+/// Whether `c` is in the Combining Diacritical Marks block.
+///
+/// These marks continue the word before them, so that decomposed text such as
+/// "Cafe\u{301}" is not split at the accent. Every combining mark produced by
+/// case mapping is in this block.
+pub(crate) fn is_combining_diacritic(c: char) -> bool {
+    ('\u{300}'..='\u{36F}').contains(&c)
+}
+
+/// Whether `c` is a lowercase letter which has an uppercase form.
+///
+/// Only such letters start or end words at changes of case. Lowercase letters
+/// without an uppercase form, like `ĸ`, stay lowercase when a word is
+/// uppercased, so counting them would split the word again if it were
+/// converted a second time.
+fn changes_to_uppercase(c: char) -> bool {
+    c.is_ascii_lowercase() || !c.is_ascii() && c.is_lowercase() && !c.to_uppercase().eq([c])
+}
+
+/// Whether `c` is an uppercase letter which has a lowercase form.
+///
+/// See [`changes_to_uppercase`].
+fn changes_to_lowercase(c: char) -> bool {
+    c.is_ascii_uppercase() || !c.is_ascii() && c.is_uppercase() && !c.to_lowercase().eq([c])
+}
+
+/// Split the run of word characters `s[start..end]` into words at changes of
+/// case, calling `word` with the byte range of each word in `s`.
+fn segment_chunk<E, W>(
+    s: &str,
+    start: usize,
+    end: usize,
+    options: Options,
+    word: &mut W,
+) -> Result<(), E>
+where
+    W: FnMut(usize, usize) -> Result<(), E>,
 {
     /// Tracks the current 'mode' of the transformation algorithm as it scans
     /// the input string.
@@ -96,97 +872,548 @@ where
         Uppercase,
     }
 
-    let mut first_word = true;
+    let chunk = &s[start..end];
+    let mut char_indices = chunk.char_indices().peekable();
+    let mut init = 0;
+    let mut mode = WordMode::Boundary;
+    // The number of uppercase characters since the last lowercase character.
+    // Like the mode, this is not changed by uncased characters.
+    let mut capitals = 0;
+    // The previous character, so that there is no word boundary just after
+    // an apostrophe joined into the word by `Options::contractions`.
+    let mut prev = None;
 
-    for word in s.split(|c: char| !c.is_alphanumeric()) {
-        let mut char_indices = word.char_indices().peekable();
-        let mut init = 0;
-        let mut mode = WordMode::Boundary;
-
-        while let Some((i, c)) = char_indices.next() {
-            if let Some(&(next_i, next)) = char_indices.peek() {
-                // The mode including the current character, assuming the
-                // current character does not result in a word boundary.
-                let next_mode = if c.is_lowercase() {
-                    WordMode::Lowercase
-                } else if c.is_uppercase() {
-                    WordMode::Uppercase
-                } else {
-                    mode
-                };
-
-                // Word boundary after if current is not uppercase and next
-                // is uppercase
-                if next_mode == WordMode::Lowercase && next.is_uppercase() {
-                    if !first_word {
-                        boundary(f)?;
-                    }
-                    with_word(&word[init..next_i], f)?;
-                    first_word = false;
-                    init = next_i;
-                    mode = WordMode::Boundary;
-
-                // Otherwise if current and previous are uppercase and next
-                // is lowercase, word boundary before
-                } else if mode == WordMode::Uppercase && c.is_uppercase() && next.is_lowercase() {
-                    if !first_word {
-                        boundary(f)?;
-                    } else {
-                        first_word = false;
-                    }
-                    with_word(&word[init..i], f)?;
-                    init = i;
-                    mode = WordMode::Boundary;
+    while let Some((i, c)) = char_indices.next() {
+        if changes_to_lowercase(c) {
+            capitals += 1;
+        } else if changes_to_uppercase(c) {
+            capitals = 0;
+        }
 
-                // Otherwise no word boundary, just update the mode
-                } else {
-                    mode = next_mode;
-                }
+        if let Some(&(next_i, next)) = char_indices.peek() {
+            // The mode including the current character, assuming the
+            // current character does not result in a word boundary.
+            let next_mode = if changes_to_uppercase(c) {
+                WordMode::Lowercase
+            } else if changes_to_lowercase(c) {
+                WordMode::Uppercase
             } else {
-                // Collect trailing characters as a word
-                if !first_word {
-                    boundary(f)?;
-                } else {
-                    first_word = false;
-                }
-                with_word(&word[init..], f)?;
-                break;
+                mode
+            };
+
+            // No word boundary after an apostrophe, which would be left at
+            // the end of a word
+            if is_apostrophe(c) {
+                mode = next_mode;
+
+            // Otherwise word boundary after if next is uppercase and every
+            // capital begins a word
+            } else if options.split_every_capital && changes_to_lowercase(next) {
+                word(start + init, start + next_i)?;
+                init = next_i;
+                mode = WordMode::Boundary;
+
+            // Otherwise word boundary after if current is a letter and next
+            // is a digit, and numbers start words. The mode is kept, so that
+            // the case of the letters before the number still decides where
+            // the letters after it begin a word.
+            } else if options.number_starts_word && c.is_alphabetic() && next.is_numeric() {
+                word(start + init, start + next_i)?;
+                init = next_i;
+                mode = next_mode;
+
+            // Otherwise word boundary after if current is a digit and next is
+            // uppercase, and digits end words
+            } else if options.digit_then_upper_boundary
+                && c.is_numeric()
+                && changes_to_lowercase(next)
+            {
+                word(start + init, start + next_i)?;
+                init = next_i;
+                mode = WordMode::Boundary;
+                // The capitals before the digit are in the finished word.
+                capitals = 0;
+
+            // Otherwise word boundary after if current is not uppercase and
+            // next is uppercase
+            } else if next_mode == WordMode::Lowercase && changes_to_lowercase(next) {
+                word(start + init, start + next_i)?;
+                init = next_i;
+                mode = WordMode::Boundary;
+
+            // Otherwise if exactly two uppercase characters are followed by
+            // lowercase and they are to be grouped, word boundary after
+            } else if options.group_consecutive_capitals
+                && !options.split_every_capital
+                && capitals == 2
+                && changes_to_uppercase(next)
+            {
+                word(start + init, start + next_i)?;
+                init = next_i;
+                mode = WordMode::Boundary;
+
+            // Otherwise if current and previous are uppercase and next
+            // is lowercase, word boundary before, unless that is just after
+            // an apostrophe
+            } else if mode == WordMode::Uppercase
+                && changes_to_lowercase(c)
+                && changes_to_uppercase(next)
+                && !prev.map_or(false, is_apostrophe)
+            {
+                word(start + init, start + i)?;
+                init = i;
+                mode = WordMode::Boundary;
+
+            // Otherwise no word boundary, just update the mode
+            } else {
+                mode = next_mode;
             }
+        } else {
+            // Collect trailing characters as a word
+            word(start + init, end)?;
         }
+        prev = Some(c);
     }
 
     Ok(())
 }
 
-fn lowercase(s: &str, f: &mut fmt::Formatter) -> fmt::Result {
-    let mut chars = s.chars().peekable();
-    while let Some(c) = chars.next() {
-        if c == 'Σ' && chars.peek().is_none() {
+/// Replace every character which canonically decomposes into an ASCII letter
+/// and combining marks with that letter, and drop any combining marks which
+/// follow an ASCII letter. All other characters are left unchanged.
+#[cfg(feature = "unicode-normalization")]
+fn ascii_fold(s: &str) -> alloc::string::String {
+    use unicode_normalization::char::{decompose_canonical, is_combining_mark};
+
+    let mut folded = alloc::string::String::with_capacity(s.len());
+    let mut after_ascii_letter = false;
+
+    for c in s.chars() {
+        if after_ascii_letter && is_combining_mark(c) {
+            continue;
+        }
+
+        let mut base = None;
+        let mut only_marks = true;
+        decompose_canonical(c, |d| {
+            if base.is_none() {
+                base = Some(d);
+            } else if !is_combining_mark(d) {
+                only_marks = false;
+            }
+        });
+
+        match base {
+            Some(base) if base.is_ascii_alphabetic() && only_marks => folded.push(base),
+            _ => folded.push(c),
+        }
+        after_ascii_letter = folded.ends_with(|c: char| c.is_ascii_alphabetic());
+    }
+
+    folded
+}
+
+/// Lowercase a word.
+///
+/// A capital sigma is lowercased to a final sigma following Unicode's
+/// `Final_Sigma` condition: when it is preceded by a cased letter and not
+/// followed by one, skipping any combining marks in between. The condition is
+/// evaluated within the word being written, not the whole input, because that
+/// is where the sigma ends up in the output: "ΟΔΟΣΟδος" is written as
+/// "οδος_οδος" in snake case, although the first sigma is followed by a letter
+/// in the input. With `Options::final_sigma` turned off, a capital sigma is
+/// always lowercased to `σ`.
+fn lowercase(s: &str, options: Options, f: &mut fmt::Formatter) -> fmt::Result {
+    lowercase_after(None, s, options, f)
+}
+
+/// Lowercase the rest of a word, after the character `prev`.
+fn lowercase_after(
+    mut prev: Option<char>,
+    s: &str,
+    options: Options,
+    f: &mut fmt::Formatter,
+) -> fmt::Result {
+    for (i, c) in s.char_indices() {
+        if c == 'Σ'
+            && options.final_sigma
+            && prev.map_or(false, is_cased)
+            && !s[i + c.len_utf8()..]
+                .chars()
+                .find(|&c| !is_case_ignorable(c))
+                .map_or(false, is_cased)
+        {
             write!(f, "ς")?;
+        } else if c.is_lowercase() {
+            // Characters which are already lowercase are their own lowercase
+            // mapping, so they can be written directly.
+            f.write_char(c)?;
         } else {
             write!(f, "{}", c.to_lowercase())?;
         }
+        if !is_case_ignorable(c) {
+            prev = Some(c);
+        }
     }
 
     Ok(())
 }
 
+fn is_cased(c: char) -> bool {
+    c.is_lowercase() || c.is_uppercase()
+}
+
+/// Whether `c` is skipped when looking for the cased letters around a sigma.
+///
+/// These are the characters with Unicode's `Case_Ignorable` property which can
+/// be inside a word: combining diacritical marks, and the apostrophes, middle
+/// dot and zero width non-joiner kept by some options.
+fn is_case_ignorable(c: char) -> bool {
+    is_combining_diacritic(c) || is_apostrophe(c) || c == '·' || c == '\u{200C}'
+}
+
 fn uppercase(s: &str, f: &mut fmt::Formatter) -> fmt::Result {
     for c in s.chars() {
-        write!(f, "{}", c.to_uppercase())?;
+        // Characters which are already uppercase are their own uppercase
+        // mapping, so they can be written directly.
+        if c.is_uppercase() {
+            f.write_char(c)?;
+        } else {
+            write!(f, "{}", c.to_uppercase())?;
+        }
     }
 
     Ok(())
 }
 
-fn capitalize(s: &str, f: &mut fmt::Formatter) -> fmt::Result {
+fn capitalize(s: &str, options: Options, f: &mut fmt::Formatter) -> fmt::Result {
+    // In Dutch, the digraph "ij" at the start of a word is capitalized as a
+    // whole.
+    if options.locale == Locale::Dutch {
+        let mut chars = s.chars();
+        if let (Some('i' | 'I'), Some('j' | 'J')) = (chars.next(), chars.next()) {
+            write!(f, "IJ")?;
+            return lowercase_after(Some('J'), chars.as_str(), options, f);
+        }
+    }
+
     let mut char_indices = s.char_indices();
     if let Some((_, c)) = char_indices.next() {
         write!(f, "{}", c.to_uppercase())?;
         if let Some((i, _)) = char_indices.next() {
-            lowercase(&s[i..], f)?;
+            lowercase_after(Some(c), &s[i..], options, f)?;
         }
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{boundaries_into, classified_words, decompose, segment_and_render, WordKind};
+    use crate::{
+        AsAdaCase, AsKebabCase, AsLowerCamelCase, AsShoutyKebabCase, AsShoutySnakeCase,
+        AsSnakeCase, AsTitleCase, AsTrainCase, AsUpperCamelCase, Options, ToKebabCase,
+        ToShoutyKebabCase, ToShoutySnakeCase, ToSnakeCase, ToTitleCase,
+    };
+    use alloc::{
+        format,
+        string::{String, ToString},
+        vec::Vec,
+    };
+    use core::fmt;
+
+    #[test]
+    fn boundaries_reconstruct_words() {
+        let mut buf = Vec::new();
+        for s in [
+            "CamelCase",
+            "MixedUP CamelCase, with some Spaces",
+            "this-contains_ ALLKinds OfWord_Boundaries",
+            "XΣXΣ baﬄe",
+            "ABC123dEEf456FOO",
+            "__",
+            "",
+        ] {
+            boundaries_into(s, &mut buf);
+            let words: Vec<String> = buf
+                .iter()
+                .map(|&(start, end)| s[start..end].to_lowercase())
+                .collect();
+            assert_eq!(words.join("_"), s.to_snake_case(), "{}", s);
+        }
+    }
+
+    #[test]
+    fn boundaries_into_clears_buffer() {
+        let mut buf = Vec::from([(0, 1)]);
+        boundaries_into("", &mut buf);
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn cased_characters_map_to_themselves() {
+        // `lowercase` and `uppercase` write characters which are already in
+        // the target case without mapping them, which relies on this.
+        for c in (0..=0x10FFFF).filter_map(char::from_u32) {
+            if c.is_lowercase() {
+                assert!(c.to_lowercase().eq([c]), "{:?}", c);
+            }
+            if c.is_uppercase() {
+                assert!(c.to_uppercase().eq([c]), "{:?}", c);
+            }
+        }
+    }
+
+    #[test]
+    fn decompose_words() {
+        assert_eq!(decompose("CamelCase"), ["Camel", "Case"]);
+        assert_eq!(
+            decompose("MixedUP CamelCase, with some Spaces"),
+            ["Mixed", "UP", "Camel", "Case", "with", "some", "Spaces"]
+        );
+        assert_eq!(
+            decompose("ABC123dEEf456FOO"),
+            ["ABC123d", "E", "Ef456", "FOO"]
+        );
+        assert_eq!(decompose("XΣXΣ baﬄe"), ["XΣXΣ", "baﬄe"]);
+        assert_eq!(decompose("_a__b_"), ["a", "b"]);
+        assert_eq!(decompose("__"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn classify_words() {
+        assert_eq!(
+            classified_words("FIELD_NAME11 FieldNamE11 ΣΑΣ a 2nd 3 ファイル"),
+            [
+                ("FIELD", WordKind::Acronym),
+                ("NAME11", WordKind::Acronym),
+                ("Field", WordKind::Capitalized),
+                ("Nam", WordKind::Capitalized),
+                ("E11", WordKind::Capitalized),
+                ("ΣΑΣ", WordKind::Acronym),
+                ("a", WordKind::Lowercase),
+                ("2nd", WordKind::Lowercase),
+                ("3", WordKind::Numeric),
+                ("ファイル", WordKind::Uncased),
+            ]
+        );
+        assert_eq!(
+            classified_words("ABC123dEEf456FOO ĸA Aĸ ĸ"),
+            [
+                ("ABC123d", WordKind::Mixed),
+                ("E", WordKind::Capitalized),
+                ("Ef456", WordKind::Capitalized),
+                ("FOO", WordKind::Acronym),
+                ("ĸA", WordKind::Capitalized),
+                ("Aĸ", WordKind::Capitalized),
+                ("ĸ", WordKind::Uncased),
+            ]
+        );
+    }
+
+    #[test]
+    fn trailing_separator() {
+        let options = Options::new().trailing_separator(true);
+        let s = "foo bar";
+
+        assert_eq!(AsSnakeCase(s).with_options(options).to_string(), "foo_bar_");
+        assert_eq!(AsKebabCase(s).with_options(options).to_string(), "foo-bar-");
+        assert_eq!(
+            AsShoutySnakeCase(s).with_options(options).to_string(),
+            "FOO_BAR_"
+        );
+        assert_eq!(
+            AsShoutyKebabCase(s).with_options(options).to_string(),
+            "FOO-BAR-"
+        );
+        assert_eq!(AsTrainCase(s).with_options(options).to_string(), "Foo-Bar-");
+        assert_eq!(AsAdaCase(s).with_options(options).to_string(), "Foo_Bar_");
+        assert_eq!(AsTitleCase(s).with_options(options).to_string(), "Foo Bar ");
+        assert_eq!(
+            AsUpperCamelCase(s).with_options(options).to_string(),
+            "FooBar"
+        );
+        assert_eq!(
+            AsLowerCamelCase(s).with_options(options).to_string(),
+            "fooBar"
+        );
+
+        assert_eq!(AsSnakeCase("foo").with_options(options).to_string(), "foo_");
+        assert_eq!(AsSnakeCase("").with_options(options).to_string(), "");
+        assert_eq!(AsSnakeCase(" _ ").with_options(options).to_string(), "");
+        assert_eq!(
+            AsSnakeCase("a_b")
+                .with_options(options.separator('.'))
+                .to_string(),
+            "a.b."
+        );
+        assert_eq!(
+            AsSnakeCase("my file.txt")
+                .with_options(options.preserve_extension(true))
+                .to_string(),
+            "my_file_.txt"
+        );
+        let small_words = ["of"];
+        assert_eq!(
+            AsTitleCase("lord of the rings of")
+                .with_options(options.small_words(&small_words))
+                .to_string(),
+            "Lord of The Rings Of "
+        );
+    }
+
+    #[test]
+    fn segment_and_render_matches_cases() {
+        let render = |s: &str, sep: &str| {
+            let mut out = String::new();
+            segment_and_render(
+                s,
+                sep,
+                |word, first, out: &mut String| {
+                    if !first {
+                        out.push('|');
+                    }
+                    out.push_str(word);
+                    Ok(())
+                },
+                &mut out,
+            )
+            .unwrap();
+            out
+        };
+
+        assert_eq!(render("XMLHttpRequest", "_"), "XML_|Http_|Request");
+        assert_eq!(render("  foo--bar ", ""), "foo|bar");
+        assert_eq!(render("", "_"), "");
+
+        let mut out = String::new();
+        let mut calls = 0;
+        let result = segment_and_render(
+            "foo bar baz",
+            "_",
+            |_, _, _: &mut String| {
+                calls += 1;
+                Err(fmt::Error)
+            },
+            &mut out,
+        );
+        assert_eq!(result, Err(fmt::Error));
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn mixed_separators() {
+        let separators = [
+            " ", "_", "-", ".", "/", ",", ":", "\t", "__", "--", "-_-", "_-_", " - ", "!?", "~+*",
+        ];
+        for a in separators {
+            for b in separators {
+                let s = format!("{a}foo{b}bar{a}{b}baz{b}", a = a, b = b);
+                assert_eq!(s.to_snake_case(), "foo_bar_baz", "{:?}", s);
+                assert_eq!(s.to_kebab_case(), "foo-bar-baz", "{:?}", s);
+            }
+        }
+    }
+
+    // Uppercasing can leave lowercase letters without an uppercase form, and
+    // can add combining marks; neither may add a word boundary. Only cased
+    // characters are changed by uppercasing.
+    #[test]
+    fn shouty_idempotent_for_every_char() {
+        let cased = |c: &char| c.is_lowercase() || c.is_uppercase();
+        for c in (0..=0x10FFFF).filter_map(char::from_u32).filter(cased) {
+            let s = format!("a{}B{}", c, c);
+
+            let snake = s.to_shouty_snake_case();
+            assert_eq!(snake.to_shouty_snake_case(), snake, "{:?}", c);
+
+            let kebab = s.to_shouty_kebab_case();
+            assert_eq!(kebab.to_shouty_kebab_case(), kebab, "{:?}", c);
+        }
+    }
+
+    // Converting a very long input takes a few seconds, so this is only run
+    // with `cargo test -- --ignored`. Each conversion writes its output as it
+    // goes, and would take hours if any of them were quadratic.
+    #[test]
+    #[ignore]
+    fn long_input() {
+        let s = "fooBarΣΑΣ ".repeat(1 << 20);
+
+        assert_eq!(s.to_snake_case().len(), (15 << 20) - 1);
+        assert_eq!(s.to_title_case().len(), (15 << 20) - 1);
+
+        let brands = ["FooBarSaz"];
+        let small_words = ["bar"];
+        let options = Options::new()
+            .brands(&brands)
+            .small_words(&small_words)
+            .group_consecutive_capitals(true);
+        assert_eq!(
+            AsTitleCase(&s).with_options(options).to_string().len(),
+            (15 << 20) - 1
+        );
+
+        let camel = "aB".repeat(4 << 20);
+        let options = Options::new().brands(&brands);
+        assert_eq!(
+            AsSnakeCase(&camel).with_options(options).to_string().len(),
+            12 << 20
+        );
+    }
+
+    // Replacing separators with other separators never changes the words. The
+    // inputs are generated from a fixed seed, so that the test is
+    // deterministic. Some characters are only separators in some positions, or
+    // with some options, and are covered by the tests of those options: a zero
+    // width non-joiner between letters, `.` between digits with
+    // `keep_dotted_numbers`, `·` with `keep_middle_dot`, `::` with
+    // `keep_namespace_separators`, and apostrophes with `contractions`.
+    #[test]
+    fn separator_substitution() {
+        const CHARS: [char; 16] = [
+            'a', 'B', 'c', 'D', 'é', 'Σ', '1', '2', '\u{301}', '\u{200C}', 'ĸ', '_', '-', ' ', '.',
+            '·',
+        ];
+        const SEPARATORS: [char; 8] = ['_', '-', ' ', '.', '·', '\'', ':', '/'];
+
+        let mut state: u32 = 0x2545_f491;
+        let mut next = |n: usize| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state as usize % n
+        };
+
+        for _ in 0..2000 {
+            let len = next(16);
+            let s: String = (0..len).map(|_| CHARS[next(CHARS.len())]).collect();
+            let replacement = SEPARATORS[next(SEPARATORS.len())];
+            let replaced: String = s
+                .chars()
+                .map(|c| {
+                    if SEPARATORS.contains(&c) {
+                        replacement
+                    } else {
+                        c
+                    }
+                })
+                .collect();
+            assert_eq!(
+                decompose(&s),
+                decompose(&replaced),
+                "{:?} {:?}",
+                s,
+                replaced
+            );
+            assert_eq!(
+                s.to_snake_case(),
+                replaced.to_snake_case(),
+                "{:?} {:?}",
+                s,
+                replaced
+            );
+        }
+    }
+}
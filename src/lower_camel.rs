@@ -1,11 +1,11 @@
-use core::fmt;
+use core::{cell::Cell, fmt};
 
 use alloc::{
     borrow::ToOwned,
     string::{String, ToString},
 };
 
-use crate::{capitalize, lowercase, transform};
+use crate::{capitalize, lowercase, transform_words, AsUpperCamelCase, Options, WithOptions};
 
 /// This trait defines a lower camel case conversion.
 ///
@@ -41,36 +41,121 @@ impl ToLowerCamelCase for str {
 /// let sentence = "It is we who built these palaces and cities.";
 /// assert_eq!(format!("{}", AsLowerCamelCase(sentence)), "itIsWeWhoBuiltThesePalacesAndCities");
 /// ```
+#[derive(Clone, Copy)]
 pub struct AsLowerCamelCase<T: AsRef<str>>(pub T);
 
+impl<T: AsRef<str>> AsLowerCamelCase<T> {
+    /// Configure this conversion with the given [`Options`].
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use heck::{AsLowerCamelCase, Options};
+    ///
+    /// let options = Options::new().keep_double_underscores(true);
+    /// assert_eq!(format!("{}", AsLowerCamelCase("FOO__BAR_BAZ").with_options(options)), "foo_BarBaz");
+    /// ```
+    pub fn with_options(self, options: Options) -> WithOptions<Self> {
+        WithOptions {
+            case: self,
+            options,
+        }
+    }
+}
+
 impl<T: AsRef<str>> fmt::Display for AsLowerCamelCase<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let mut first = true;
-        transform(
-            self.0.as_ref(),
+        fmt::Display::fmt(
+            &AsLowerCamelCase(self.0.as_ref()).with_options(Options::new()),
+            f,
+        )
+    }
+}
+
+impl<T: AsRef<str>> From<AsLowerCamelCase<T>> for String {
+    fn from(case: AsLowerCamelCase<T>) -> String {
+        case.to_string()
+    }
+}
+
+impl<T: AsRef<str>> fmt::Display for WithOptions<'_, AsLowerCamelCase<T>> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let options = self.options;
+        // A boundary comes before every word but the first. This is tracked
+        // there, as a word with a spelling in the acronym map is written
+        // without calling the word closure.
+        let first = Cell::new(true);
+        transform_words(
+            self.case.0.as_ref(),
+            options,
+            true,
             |s, f| {
-                if first {
-                    first = false;
-                    lowercase(s, f)
+                if first.get() {
+                    lowercase(s, options, f)
                 } else {
-                    capitalize(s, f)
+                    capitalize(s, options, f)
+                }
+            },
+            |separator, f| {
+                first.set(false);
+                if options.keep_double_underscores && separator.chars().count() >= 2 {
+                    write!(f, "_")
+                } else {
+                    Ok(())
                 }
             },
-            |_| Ok(()),
             f,
         )
     }
 }
 
+/// Convert a string to camel case, following the capitalization of its first
+/// letter.
+///
+/// If the first cased character of the string is uppercase, it is converted to
+/// UpperCamelCase, and otherwise to lowerCamelCase. Digits, separators and
+/// letters which have no case before it are skipped, so `_Foo` and `2Fast` are
+/// converted to UpperCamelCase. A string with no cased characters is converted
+/// to lowerCamelCase.
+///
+/// ## Example:
+///
+/// ```
+/// use heck::to_smart_camel_case;
+///
+/// assert_eq!(to_smart_camel_case("foo_bar"), "fooBar");
+/// assert_eq!(to_smart_camel_case("Foo_bar"), "FooBar");
+/// ```
+pub fn to_smart_camel_case(s: &str) -> String {
+    let upper = s
+        .chars()
+        .find(|c| c.is_lowercase() || c.is_uppercase())
+        .map_or(false, char::is_uppercase);
+    if upper {
+        AsUpperCamelCase(s).to_string()
+    } else {
+        AsLowerCamelCase(s).to_string()
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::ToLowerCamelCase;
+    use super::{to_smart_camel_case, AsLowerCamelCase, ToLowerCamelCase};
+    use crate::{AsSnakeCase, AsUpperCamelCase, Options};
+    use alloc::string::ToString;
 
     macro_rules! t {
-        ($t:ident : $s1:expr => $s2:expr) => {
+        ($t:ident : $($s1:expr => $s2:expr),+ $(,)?) => {
+            #[test]
+            fn $t() {
+                $(assert_eq!($s1.to_lower_camel_case(), $s2);)+
+            }
+        };
+        ($t:ident : $options:expr ; $($s1:expr => $s2:expr),+ $(,)?) => {
             #[test]
             fn $t() {
-                assert_eq!($s1.to_lower_camel_case(), $s2)
+                let options = $options;
+                $(assert_eq!(AsLowerCamelCase($s1).with_options(options).to_string(), $s2);)+
             }
         };
     }
@@ -85,4 +170,84 @@ mod tests {
     t!(test8: "this-contains_ ALLKinds OfWord_Boundaries" => "thisContainsAllKindsOfWordBoundaries");
     t!(test9: "XΣXΣ baﬄe" => "xσxςBaﬄe");
     t!(test10: "XMLHttpRequest" => "xmlHttpRequest");
+    t!(test11: "123ABC def" => "123abcDef");
+    t!(test12: "room 101b" => "room101b");
+
+    t!(keep_double_underscores: Options::new().keep_double_underscores(true);
+        "foo_bar" => "fooBar",
+        "foo__bar" => "foo_Bar",
+        "FOO___BAR_BAZ" => "foo_BarBaz",
+        "foo-_bar" => "foo_Bar",
+        "__foo__" => "foo",
+    );
+
+    t!(keep_double_underscores_default: "foo__bar" => "fooBar");
+
+    t!(acronym_map: Options::new().acronym_map(&[("ios", "iOS"), ("ebay", "eBay"), ("ios", "IOS")]);
+        "ios_app" => "iOSApp",
+        "iosApp" => "iOSApp",
+        "app for IOS" => "appForiOS",
+        "EBAY_SELLER" => "eBaySeller",
+        "iOSApp" => "iOSApp",
+        "i_os_app" => "iOsApp",
+        "biosUpdate" => "biosUpdate",
+    );
+
+    t!(acronym_map_default: "ios_app" => "iosApp");
+
+    #[test]
+    fn smart_camel_case() {
+        assert_eq!(to_smart_camel_case("fooBar"), "fooBar");
+        assert_eq!(to_smart_camel_case("FooBar"), "FooBar");
+        assert_eq!(to_smart_camel_case("foo_bar"), "fooBar");
+        assert_eq!(to_smart_camel_case("Foo_bar"), "FooBar");
+        assert_eq!(to_smart_camel_case("XMLHttpRequest"), "XmlHttpRequest");
+        assert_eq!(to_smart_camel_case("__Foo_bar"), "FooBar");
+        assert_eq!(to_smart_camel_case("2fast 4you"), "2fast4you");
+        assert_eq!(to_smart_camel_case("2Fast 4you"), "2fast4you");
+        assert_eq!(to_smart_camel_case("한국어 Text"), "한국어Text");
+        assert_eq!(to_smart_camel_case(""), "");
+    }
+
+    t!(keep_leading_underscore:
+        Options::new().keep_leading_underscore(true).keep_double_underscores(true);
+        "_internal_value" => "_internalValue",
+        "__private__field" => "__private_Field",
+    );
+
+    #[test]
+    fn lowercase_leading_acronym() {
+        let map = [("url", "URL"), ("ios", "iOS"), ("http2", "HTTP2")];
+        let options = Options::new().acronym_map(&map);
+        let convert = |s: &str, options| AsLowerCamelCase(s).with_options(options).to_string();
+
+        assert_eq!(convert("url_parser", options), "urlParser");
+        assert_eq!(convert("URLParser", options), "urlParser");
+        assert_eq!(convert("parse_url", options), "parseURL");
+        assert_eq!(convert("http2_client", options), "http2Client");
+        assert_eq!(convert("ios_app", options), "iOSApp");
+        assert_eq!(
+            convert("url_parser", Options::default().acronym_map(&map)),
+            "urlParser"
+        );
+
+        let options = options.lowercase_leading_acronym(false);
+        assert_eq!(convert("url_parser", options), "URLParser");
+        assert_eq!(convert("http2_client", options), "HTTP2Client");
+        assert_eq!(convert("ios_app", options), "iOSApp");
+
+        assert_eq!(
+            AsUpperCamelCase("url_parser")
+                .with_options(Options::new().acronym_map(&map))
+                .to_string(),
+            "URLParser"
+        );
+        assert_eq!(
+            AsSnakeCase("URL_parser")
+                .with_options(Options::new().acronym_map(&map))
+                .to_string(),
+            "URL_parser"
+        );
+        assert_eq!("URLParser".to_lower_camel_case(), "urlParser");
+    }
 }
@@ -0,0 +1,814 @@
+use core::fmt;
+
+use alloc::string::{String, ToString};
+
+/// Options which configure a case conversion.
+///
+/// Options are applied to a conversion with the `with_options` method of the
+/// case conversion wrappers. The default options change nothing, so
+/// converting with `Options::new()` is the same as converting without any
+/// options.
+///
+/// ## Example:
+///
+/// ```
+/// use heck::{AsUpperCamelCase, Options};
+///
+/// let options = Options::new().keep_double_underscores(true);
+/// assert_eq!(AsUpperCamelCase("FOO__BAR").with_options(options).to_string(), "Foo_Bar");
+/// ```
+///
+/// ## Order of application
+///
+/// Options may be given in any order, and are always applied in this one:
+///
+/// 1. [`Options::strip_prefix`], then [`Options::strip_suffix`], on the input
+///    exactly as it was given, and then [`Options::keep_leading_underscore`].
+/// 2. `Options::ascii_fold`, with the `unicode-normalization` feature, on
+///    what is left of the input.
+/// 3. [`Options::preserve_extension`], which sets the extension aside.
+/// 4. Splitting the input into words, which the other options that keep
+///    characters or change where words begin take part in.
+/// 5. [`Options::acronym_map`] and [`Options::brands`], which join and respell
+///    words.
+/// 6. [`Options::max_words`], which counts the words after they have been
+///    joined.
+/// 7. Converting each word and writing the separators between them.
+///
+/// ```
+/// use heck::{AsSnakeCase, Options};
+///
+/// let options = Options::new()
+///     .max_words(2)
+///     .acronym_map(&[("ios", "iOS")])
+///     .strip_prefix("lib");
+/// assert_eq!(AsSnakeCase("libIOSImageCacheTools").with_options(options).to_string(), "iOS_image");
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Options<'a> {
+    pub(crate) keep_double_underscores: bool,
+    pub(crate) roman_numerals: bool,
+    pub(crate) keep_namespace_separators: bool,
+    pub(crate) keep_middle_dot: bool,
+    pub(crate) preserve_extension: bool,
+    pub(crate) script_boundary: bool,
+    pub(crate) merge_single_letter_words: bool,
+    pub(crate) keep_dotted_numbers: bool,
+    pub(crate) acronym_map: &'a [(&'a str, &'a str)],
+    pub(crate) keep_dashes: bool,
+    pub(crate) small_words: &'a [&'a str],
+    pub(crate) strip_prefix: &'a str,
+    pub(crate) strip_suffix: &'a str,
+    pub(crate) locale: Locale,
+    pub(crate) separator: Option<char>,
+    pub(crate) group_consecutive_capitals: bool,
+    pub(crate) brands: &'a [&'a str],
+    pub(crate) max_words: Option<usize>,
+    pub(crate) emoji_words: bool,
+    pub(crate) split_every_capital: bool,
+    pub(crate) trailing_separator: bool,
+    pub(crate) math_symbols: MathSymbols,
+    pub(crate) contractions: ContractionPolicy,
+    pub(crate) protocol_tokens: &'a [&'a str],
+    pub(crate) digit_then_upper_boundary: bool,
+    pub(crate) final_sigma: bool,
+    pub(crate) keep_all_caps: bool,
+    pub(crate) force_lowercase: bool,
+    pub(crate) number_starts_word: bool,
+    pub(crate) keep_leading_underscore: bool,
+    pub(crate) lowercase_leading_acronym: bool,
+    #[cfg(feature = "unicode-normalization")]
+    pub(crate) ascii_fold: bool,
+}
+
+impl<'a> Options<'a> {
+    /// Create the default options.
+    pub fn new() -> Self {
+        Options {
+            keep_double_underscores: false,
+            roman_numerals: false,
+            keep_namespace_separators: false,
+            keep_middle_dot: false,
+            preserve_extension: false,
+            script_boundary: false,
+            merge_single_letter_words: false,
+            keep_dotted_numbers: false,
+            acronym_map: &[],
+            keep_dashes: false,
+            small_words: &[],
+            strip_prefix: "",
+            strip_suffix: "",
+            locale: Locale::Root,
+            separator: None,
+            group_consecutive_capitals: false,
+            brands: &[],
+            max_words: None,
+            emoji_words: false,
+            split_every_capital: false,
+            trailing_separator: false,
+            math_symbols: MathSymbols::Keep,
+            contractions: ContractionPolicy::Split,
+            protocol_tokens: &[],
+            digit_then_upper_boundary: false,
+            final_sigma: true,
+            keep_all_caps: false,
+            force_lowercase: false,
+            number_starts_word: false,
+            keep_leading_underscore: false,
+            lowercase_leading_acronym: true,
+            #[cfg(feature = "unicode-normalization")]
+            ascii_fold: false,
+        }
+    }
+
+    /// Keep an underscore in camel case output between words which were
+    /// separated by a run of two or more separator characters, such as `__` or
+    /// `-_`.
+    ///
+    /// This distinguishes `FOO__BAR` (`Foo_Bar`) from `FOO_BAR` (`FooBar`),
+    /// which matters for names such as C macros that use a double underscore
+    /// on purpose. It only affects UpperCamelCase and lowerCamelCase.
+    pub fn keep_double_underscores(mut self, yes: bool) -> Self {
+        self.keep_double_underscores = yes;
+        self
+    }
+
+    /// Set whether a first word in lowerCamelCase with an all uppercase
+    /// spelling in the acronym map or the brands is lowercased. This is on by
+    /// default.
+    ///
+    /// By convention lowerCamelCase begins with a lowercase letter, so with
+    /// the acronym map `url` → `URL`, `url_parser` becomes `urlParser`, and
+    /// the acronym is only kept after the first word: `parse_url` becomes
+    /// `parseURL`. Turning this off keeps the acronym in the first word too,
+    /// giving `URLParser`. Spellings with lowercase letters, like `iOS`, are
+    /// always kept. It only affects lowerCamelCase.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use heck::{AsLowerCamelCase, Options};
+    ///
+    /// let options = Options::new().acronym_map(&[("url", "URL")]);
+    /// assert_eq!(AsLowerCamelCase("url_parser").with_options(options).to_string(), "urlParser");
+    /// let options = options.lowercase_leading_acronym(false);
+    /// assert_eq!(AsLowerCamelCase("url_parser").with_options(options).to_string(), "URLParser");
+    /// ```
+    pub fn lowercase_leading_acronym(mut self, yes: bool) -> Self {
+        self.lowercase_leading_acronym = yes;
+        self
+    }
+
+    /// Keep the underscores at the start of the input, which mark private or
+    /// unused names in many languages, so that `_internalValue` becomes
+    /// `_internal_value` in snake_case and `__init` stays `__init`.
+    ///
+    /// Every leading underscore is kept, in every case, and the rest of the
+    /// input is converted as usual. Underscores before the first word are not
+    /// between words, so [`Options::keep_double_underscores`] does not apply
+    /// to them; leading characters other than underscores are still dropped.
+    pub fn keep_leading_underscore(mut self, yes: bool) -> Self {
+        self.keep_leading_underscore = yes;
+        self
+    }
+
+    /// Write words which look like roman numerals in uppercase in Title Case,
+    /// so that `henry viii` becomes `Henry VIII`.
+    ///
+    /// A word is taken to be a roman numeral if it is a numeral between 1 and
+    /// 399 in its canonical form, such as `iv` or `xiv` but not `iiii`. Larger
+    /// numerals are not recognized because `d` and `m` make too many ordinary
+    /// words look like numerals (`mix`, `di`). Some words are still valid
+    /// numerals, and are uppercased as well: `vi`, `li`, `liv`, `civ`.
+    pub fn roman_numerals(mut self, yes: bool) -> Self {
+        self.roman_numerals = yes;
+        self
+    }
+
+    /// Keep words which are all capitals in the input, like `NASA`, in
+    /// capitals in Title Case.
+    ///
+    /// A word is kept if it has at least two cased letters and all of them
+    /// are uppercase, as for [`WordKind::Acronym`](crate::WordKind::Acronym).
+    /// Names with mixed case, like `iPhone`, are split by segmentation, and
+    /// are kept with [`Options::brands`] instead. The two compose: a word with
+    /// a spelling from the brands or the acronym map is written with that
+    /// spelling, even if it is all capitals. It only affects Title Case.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use heck::{AsTitleCase, Options};
+    ///
+    /// let options = Options::new().keep_all_caps(true).brands(&["iPhone"]);
+    /// let title = AsTitleCase("iPhone 15 pro by NASA").with_options(options);
+    /// assert_eq!(title.to_string(), "iPhone 15 Pro By NASA");
+    /// ```
+    pub fn keep_all_caps(mut self, yes: bool) -> Self {
+        self.keep_all_caps = yes;
+        self
+    }
+
+    /// Write every word in lowercase, after all other options have been
+    /// applied, for keys which are compared without regard to case.
+    ///
+    /// This overrides the way the case writes words, and every option which
+    /// changes it, such as [`Options::acronym_map`], [`Options::brands`] and
+    /// [`Options::keep_all_caps`]: every word is written as in snake_case.
+    /// Separators are not changed, so kebab-case stays kebab-case, and the
+    /// camel cases are written with no separators at all.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use heck::{AsKebabCase, Options};
+    ///
+    /// let options = Options::new().brands(&["GitHub"]);
+    /// assert_eq!(AsKebabCase("github_url").with_options(options).to_string(), "GitHub-url");
+    /// let options = options.force_lowercase(true);
+    /// assert_eq!(AsKebabCase("github_url").with_options(options).to_string(), "github-url");
+    /// ```
+    pub fn force_lowercase(mut self, yes: bool) -> Self {
+        self.force_lowercase = yes;
+        self
+    }
+
+    /// Keep `::` between words in snake_case output, so that qualified names
+    /// like `MyModule::SomeType` become `my_module::some_type`.
+    ///
+    /// Words on either side of a `::` are converted as usual. A leading or
+    /// trailing `::` is dropped like any other separator. It only affects
+    /// snake_case.
+    pub fn keep_namespace_separators(mut self, yes: bool) -> Self {
+        self.keep_namespace_separators = yes;
+        self
+    }
+
+    /// Treat a middle dot (`·`, U+00B7) between two letters or digits as part
+    /// of the word, rather than as a separator.
+    ///
+    /// This keeps the Catalan geminate l together, so that `Paral·lel` becomes
+    /// `paral·lel` in snake_case instead of `paral_lel`. A middle dot next to
+    /// any other character is still a separator.
+    pub fn keep_middle_dot(mut self, yes: bool) -> Self {
+        self.keep_middle_dot = yes;
+        self
+    }
+
+    /// Keep a file extension at the end of the input, lowercased, and only
+    /// convert the rest, so that `MyCoolFile.TXT` becomes `my_cool_file.txt`
+    /// in snake_case.
+    ///
+    /// The extension is the text after the last `.`, if it is one to five
+    /// ASCII letters and digits including at least one letter, and there is
+    /// some text before the `.`. With several dots only the last one is kept:
+    /// `archive.tar.gz` becomes `archive_tar.gz`. Names such as `.bashrc` or
+    /// `v1.0` have no extension, and are converted as usual. If no words are
+    /// written, for example with [`Options::max_words`] set to zero, the
+    /// extension is not written either. [`convert_path`](crate::convert_path)
+    /// lowercases the extension of a path in the same way.
+    pub fn preserve_extension(mut self, yes: bool) -> Self {
+        self.preserve_extension = yes;
+        self
+    }
+
+    /// Start a new word where the text changes between Han, Kana or Hangul
+    /// and Latin, Greek or Cyrillic, so that `ファイルURL` becomes `ファイル_url`
+    /// in snake_case instead of `ファイルurl`.
+    ///
+    /// Han, Kana and Hangul text is still never split into words on its own,
+    /// as it is written without spaces. Digits and characters of other scripts
+    /// do not cause a boundary, and stay with the characters before them.
+    pub fn script_boundary(mut self, yes: bool) -> Self {
+        self.script_boundary = yes;
+        self
+    }
+
+    /// Join a word of a single letter to the word after it, so that
+    /// `ABC123dEEf456` becomes `Abc123dEef456` in UpperCamelCase instead of
+    /// `Abc123dEEf456`.
+    ///
+    /// Only words split at a change of case are joined; a letter separated
+    /// from the next word by a space or other separator stays a word of its
+    /// own. This also joins a one letter acronym to a capitalized word, so
+    /// `AString` becomes `astring` in snake_case instead of `a_string`.
+    pub fn merge_single_letter_words(mut self, yes: bool) -> Self {
+        self.merge_single_letter_words = yes;
+        self
+    }
+
+    /// Treat a `.` between two ASCII digits as part of the word, so that
+    /// version numbers like `2.0` or `1.2.3` are not split.
+    ///
+    /// Like any other digits, the version stays with the characters before
+    /// it: `Parser2.0Engine` becomes `parser2.0_engine` in snake_case, and
+    /// `parser2.0engine` is left as one word. A `.` next to any other
+    /// character is still a separator.
+    pub fn keep_dotted_numbers(mut self, yes: bool) -> Self {
+        self.keep_dotted_numbers = yes;
+        self
+    }
+
+    /// Write words with a special spelling exactly as given.
+    ///
+    /// Each entry is a pair of a word and its spelling. A word which is equal
+    /// to the first string of a pair, ignoring case, is replaced with the
+    /// second string in every case and at every position, even as the first
+    /// word in lowerCamelCase: `ios_app` becomes `iOSApp` rather than
+    /// `iosApp`.
+    /// If several pairs match a word, the first is used.
+    ///
+    /// Words which were split at changes of case are joined again if together
+    /// they match, so `iOSApp` (which is segmented `i|OS|App`) also becomes
+    /// `iOSApp`. Words with a separator between them are never joined.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use heck::{AsLowerCamelCase, Options};
+    ///
+    /// let options = Options::new().acronym_map(&[("ios", "iOS"), ("ebay", "eBay")]);
+    /// assert_eq!(AsLowerCamelCase("ios_app").with_options(options).to_string(), "iOSApp");
+    /// assert_eq!(AsLowerCamelCase("my ebay listing").with_options(options).to_string(), "myeBayListing");
+    /// ```
+    pub fn acronym_map(mut self, map: &'a [(&'a str, &'a str)]) -> Self {
+        self.acronym_map = map;
+        self
+    }
+
+    /// Keep a hyphen between words in Title Case, so that compound words like
+    /// `well-known` become `Well-Known` instead of `Well Known`.
+    ///
+    /// Only words separated by exactly one hyphen (`-`, U+002D) are joined
+    /// with a hyphen. Any other separator, including a hyphen with spaces
+    /// around it or a dash such as `–` or `—`, becomes a space as usual. With
+    /// [`Options::small_words`], the small words of a compound are lowercased
+    /// too, so that `mother-in-law` becomes `Mother-in-Law`. It only affects
+    /// Title Case.
+    pub fn keep_dashes(mut self, yes: bool) -> Self {
+        self.keep_dashes = yes;
+        self
+    }
+
+    /// Lowercase the given small words, such as articles and short
+    /// prepositions, in Title Case, except when they are the first or the last
+    /// word.
+    ///
+    /// Words are matched ignoring case. Style guides differ on which words are
+    /// small, so no list is built in. It only affects Title Case.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use heck::{AsTitleCase, Options};
+    ///
+    /// let options = Options::new().small_words(&["a", "for", "is", "of", "the"]);
+    /// assert_eq!(AsTitleCase("a tale of two cities").with_options(options).to_string(), "A Tale of Two Cities");
+    /// assert_eq!(AsTitleCase("what is it for").with_options(options).to_string(), "What is It For");
+    /// ```
+    pub fn small_words(mut self, words: &'a [&'a str]) -> Self {
+        self.small_words = words;
+        self
+    }
+
+    /// Remove `prefix` from the start of the input before converting it, if
+    /// it is there.
+    ///
+    /// The prefix is matched exactly, against the input as it is given. This
+    /// is useful for names from a C library, where every name has the same
+    /// prefix: `gtk_widget_show` becomes `WidgetShow` in UpperCamelCase with
+    /// the prefix `gtk_`. An input which is just the prefix becomes empty.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use heck::{AsUpperCamelCase, Options};
+    ///
+    /// let options = Options::new().strip_prefix("gtk_");
+    /// assert_eq!(AsUpperCamelCase("gtk_widget_show").with_options(options).to_string(), "WidgetShow");
+    /// assert_eq!(AsUpperCamelCase("g_free").with_options(options).to_string(), "GFree");
+    /// ```
+    pub fn strip_prefix(mut self, prefix: &'a str) -> Self {
+        self.strip_prefix = prefix;
+        self
+    }
+
+    /// Remove `suffix` from the end of the input before converting it, if it
+    /// is there.
+    ///
+    /// Like [`Options::strip_prefix`], the suffix is matched exactly. It is
+    /// removed after the prefix, so when the two overlap in the input only the
+    /// prefix is removed: with the prefix `foo_` and the suffix `_t`, `foo_t`
+    /// becomes `t`.
+    pub fn strip_suffix(mut self, suffix: &'a str) -> Self {
+        self.strip_suffix = suffix;
+        self
+    }
+
+    /// Capitalize words following the rules of `locale`.
+    ///
+    /// This only affects how the first letters of a word are capitalized, in
+    /// the cases which capitalize words. See [`Locale`] for the rules.
+    pub fn locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// Set whether a capital sigma at the end of a word is lowercased to the
+    /// final sigma `ς`. This is on by default.
+    ///
+    /// Following Unicode, `Σ` is lowercased to `ς` when it is preceded by a
+    /// cased letter in its word and not followed by one, and to `σ`
+    /// otherwise. Turning this off always lowercases it to `σ`, for systems
+    /// which do not make the distinction.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use heck::{AsSnakeCase, Options};
+    ///
+    /// let options = Options::new().final_sigma(false);
+    /// assert_eq!(AsSnakeCase("ΟΔΟΣ").to_string(), "οδος");
+    /// assert_eq!(AsSnakeCase("ΟΔΟΣ").with_options(options).to_string(), "οδοσ");
+    /// ```
+    pub fn final_sigma(mut self, yes: bool) -> Self {
+        self.final_sigma = yes;
+        self
+    }
+
+    /// Write `separator` between words instead of the separator of the case.
+    ///
+    /// This affects every case which separates words: snake_case,
+    /// kebab-case, SHOUTY_SNAKE_CASE, SHOUTY-KEBAB-CASE, Title Case,
+    /// Train-Case and Ada_Case. The camel cases have no separator, and are
+    /// unchanged. Separators kept by other options, such as `::` with
+    /// [`Options::keep_namespace_separators`], are still written as they are.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use heck::{AsSnakeCase, Options};
+    ///
+    /// let options = Options::new().separator('.');
+    /// assert_eq!(AsSnakeCase("MyConfigKey").with_options(options).to_string(), "my.config.key");
+    /// ```
+    pub fn separator(mut self, separator: char) -> Self {
+        self.separator = Some(separator);
+        self
+    }
+
+    /// Keep two uppercase letters followed by lowercase letters together,
+    /// rather than starting a new word at the second.
+    ///
+    /// Usually the last of several uppercase letters begins the next word if
+    /// it is followed by lowercase letters, so that `XMLHttp` is `XML|Http`.
+    /// When there are only two, as in `dEEf`, this leaves a word of a single
+    /// letter: `d|E|Ef`. With this option, exactly two uppercase letters
+    /// followed by lowercase letters are a word of their own, and the
+    /// lowercase letters begin the next word: `dEEf` is `d|EE|f`, and
+    /// `ABC123dEEf456FOO` becomes `abc123d_ee_f456_foo` in snake_case.
+    /// Three or more uppercase letters are split as usual. Digits between
+    /// uppercase letters do not separate them, so `A1Bc` is `A1B|c`, unless
+    /// [`Options::digit_then_upper_boundary`] is set.
+    pub fn group_consecutive_capitals(mut self, yes: bool) -> Self {
+        self.group_consecutive_capitals = yes;
+        self
+    }
+
+    /// Write brand names, which have their own mixed casing, exactly as given.
+    ///
+    /// A word which is equal to one of the brands, ignoring case, is replaced
+    /// with the brand in every case and at every position, like with
+    /// [`Options::acronym_map`]. Segmentation splits most such names at
+    /// changes of case, as `PostgreSQL` is `Postgre|SQL`, so consecutive words
+    /// without a separator between them are joined if together they match a
+    /// brand. The longest match starting at each word is used.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use heck::{AsSnakeCase, Options};
+    ///
+    /// let options = Options::new().brands(&["PostgreSQL", "macOS"]);
+    /// assert_eq!(AsSnakeCase("postgresqlQuery").with_options(options).to_string(), "PostgreSQL_query");
+    /// assert_eq!(AsSnakeCase("MacOSVersion").with_options(options).to_string(), "macOS_version");
+    /// ```
+    pub fn brands(mut self, brands: &'a [&'a str]) -> Self {
+        self.brands = brands;
+        self
+    }
+
+    /// Keep names which are letters followed by digits, like `ipv4` or
+    /// `sha256`, as one word.
+    ///
+    /// Digits already stay with the letters before them, so `ipv4Address` is
+    /// `ipv4|Address`, but a change of case within such a name splits it:
+    /// `IPv4` is `I|Pv4` and `ipV6` is `ip|V6`. Like with
+    /// [`Options::brands`], consecutive words without a separator between
+    /// them are joined if together they are equal to one of the tokens,
+    /// ignoring case. Unlike brands, the joined word is then converted like
+    /// any other word.
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use heck::{AsSnakeCase, AsUpperCamelCase, Options};
+    ///
+    /// let options = Options::new().protocol_tokens(&["ipv4", "ipv6"]);
+    /// assert_eq!(AsSnakeCase("IPv4Address").to_string(), "i_pv4_address");
+    /// assert_eq!(AsSnakeCase("IPv4Address").with_options(options).to_string(), "ipv4_address");
+    /// assert_eq!(AsUpperCamelCase("ipV6_route").with_options(options).to_string(), "Ipv6Route");
+    /// ```
+    pub fn protocol_tokens(mut self, tokens: &'a [&'a str]) -> Self {
+        self.protocol_tokens = tokens;
+        self
+    }
+
+    /// Write only the first `max` words of the input, and drop the rest.
+    ///
+    /// Words are counted as they are written, after any joining by other
+    /// options. With `max` set to zero, the output is empty.
+    pub fn max_words(mut self, max: usize) -> Self {
+        self.max_words = Some(max);
+        self
+    }
+
+    /// Keep emoji as words of their own, rather than dropping them as
+    /// separators, so that `happy😀face` becomes `happy_😀_face` in snake_case
+    /// instead of `happy_face`.
+    ///
+    /// An emoji sequence, such as one joined with zero width joiners, with a
+    /// skin tone modifier or a pair of regional indicators forming a flag, is
+    /// one word. Emoji are recognized by the Unicode blocks which contain
+    /// them, so some rarely used emoji are still separators.
+    pub fn emoji_words(mut self, yes: bool) -> Self {
+        self.emoji_words = yes;
+        self
+    }
+
+    /// Begin a new word at every uppercase letter, rather than keeping runs of
+    /// uppercase letters together, so that `XMLParser` becomes `x_m_l_parser`
+    /// in snake_case.
+    ///
+    /// Uncased characters stay with the letter before them as usual, so
+    /// `A1B2` is `A1|B2`. Combining marks are part of the letter they follow,
+    /// so a decomposed `A\u{301}B` is `A\u{301}|B` and the accent stays on
+    /// the `A`. This takes precedence over
+    /// [`Options::group_consecutive_capitals`].
+    pub fn split_every_capital(mut self, yes: bool) -> Self {
+        self.split_every_capital = yes;
+        self
+    }
+
+    /// Begin a new word at an uppercase letter which follows a digit.
+    ///
+    /// An uppercase letter after a digit already begins a new word if the
+    /// last letter before the digit is lowercase, so `v2API` is `v2|API`. If
+    /// it is uppercase, as in `ABC2DEF` or `A1B`, the capitals are one word,
+    /// as they would be without the digit. With this option they are split
+    /// after the digit: `ABC2|DEF` and `A1|B`.
+    pub fn digit_then_upper_boundary(mut self, yes: bool) -> Self {
+        self.digit_then_upper_boundary = yes;
+        self
+    }
+
+    /// Begin a new word at a number which follows a letter, so that
+    /// `sha256Hash` becomes `SHA_256_HASH` in SHOUTY_SNAKE_CASE rather than
+    /// `SHA256_HASH`.
+    ///
+    /// Letters after the number begin a new word if they would have without
+    /// the number, following the case of the letters before it: `md5Sum` is
+    /// `md|5|Sum`, but `sha256hash` is `sha|256hash`. The shouty cases write
+    /// every word in uppercase, so the case which split `256|Hash` is lost in
+    /// their output. Converting it again gives the same output, as
+    /// `SHA_256_HASH` is split at its underscores.
+    pub fn number_starts_word(mut self, yes: bool) -> Self {
+        self.number_starts_word = yes;
+        self
+    }
+
+    /// Write a separator after the last word as well, so that the output can
+    /// be joined to more text: `foo bar` becomes `foo_bar_` in snake_case.
+    ///
+    /// The separator is the one the case writes between words, so this does
+    /// nothing for UpperCamelCase and lowerCamelCase. It is written before a
+    /// preserved extension. If the input has no words, the output is empty
+    /// and has no separator either.
+    pub fn trailing_separator(mut self, yes: bool) -> Self {
+        self.trailing_separator = yes;
+        self
+    }
+
+    /// Set whether superscript and subscript digits are part of words. See
+    /// [`MathSymbols`] for the characters affected.
+    pub fn math_symbols(mut self, math_symbols: MathSymbols) -> Self {
+        self.math_symbols = math_symbols;
+        self
+    }
+
+    /// Set how an apostrophe within a word, as in the contraction `it's`, is
+    /// treated. See [`ContractionPolicy`] for the rules.
+    pub fn contractions(mut self, policy: ContractionPolicy) -> Self {
+        self.contractions = policy;
+        self
+    }
+
+    /// Strip accents from Latin letters before converting, so that
+    /// `Café_Menu` becomes `cafe_menu` in snake_case.
+    ///
+    /// Every character which canonically decomposes into an ASCII letter and
+    /// combining marks is replaced with that letter, and combining marks which
+    /// follow an ASCII letter are dropped. This is lossy, and only makes sense
+    /// for scripts with such base letters: letters without a decomposition
+    /// (like `ø` or `ß`) and other scripts are passed through unchanged.
+    ///
+    /// The input is folded into a copy before it is converted, so unlike the
+    /// other options this uses memory in proportion to the length of the input.
+    ///
+    /// This requires the `unicode-normalization` feature.
+    #[cfg(feature = "unicode-normalization")]
+    pub fn ascii_fold(mut self, yes: bool) -> Self {
+        self.ascii_fold = yes;
+        self
+    }
+
+    /// The spelling given for `word` in the acronym map or the brands, if any.
+    pub(crate) fn spelling(&self, word: &str) -> Option<&'a str> {
+        self.acronym_map
+            .iter()
+            .find(|(key, _)| eq_ignore_case(key, word))
+            .map(|&(_, spelling)| spelling)
+            .or_else(|| {
+                self.brands
+                    .iter()
+                    .copied()
+                    .find(|brand| eq_ignore_case(brand, word))
+            })
+    }
+
+    /// Whether consecutive words may be joined, because there is an acronym
+    /// map, brands or protocol tokens.
+    pub(crate) fn joins_words(&self) -> bool {
+        !self.acronym_map.is_empty() || !self.brands.is_empty() || !self.protocol_tokens.is_empty()
+    }
+
+    /// Whether `word` is one which consecutive words are joined into: a word
+    /// with a spelling, or a protocol token.
+    pub(crate) fn is_joined_word(&self, word: &str) -> bool {
+        self.spelling(word).is_some()
+            || self
+                .protocol_tokens
+                .iter()
+                .any(|token| eq_ignore_case(token, word))
+    }
+
+    /// The most words which together can be joined into one.
+    ///
+    /// Every word has at least one character, so words which match a key
+    /// cannot outnumber the characters of the lowercased key.
+    pub(crate) fn max_joined_words(&self) -> usize {
+        let keys = self.acronym_map.iter().map(|&(key, _)| key);
+        keys.chain(self.brands.iter().copied())
+            .chain(self.protocol_tokens.iter().copied())
+            .map(|key| key.chars().flat_map(char::to_lowercase).count())
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+impl Default for Options<'_> {
+    fn default() -> Self {
+        Options::new()
+    }
+}
+
+/// Whether `a` and `b` are equal, ignoring case.
+pub(crate) fn eq_ignore_case(a: &str, b: &str) -> bool {
+    a.chars()
+        .flat_map(char::to_lowercase)
+        .eq(b.chars().flat_map(char::to_lowercase))
+}
+
+/// A locale whose rules for capitalizing words differ from the default.
+///
+/// ## Example:
+///
+/// ```
+/// use heck::{AsTitleCase, Locale, Options};
+///
+/// let options = Options::new().locale(Locale::Dutch);
+/// assert_eq!(AsTitleCase("ijsland").with_options(options).to_string(), "IJsland");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Locale {
+    /// Capitalize the first character of a word, and lowercase the rest.
+    Root,
+    /// Capitalize the digraph `ij` at the start of a word as a whole, so that
+    /// `ijsland` becomes `IJsland` rather than `Ijsland`. Any other `ij` in a
+    /// word is lowercased as usual.
+    Dutch,
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Locale::Root
+    }
+}
+
+/// How the mathematical symbols which may continue an identifier are treated.
+///
+/// These are the characters of the Unicode `ID_Compat_Math_Continue`
+/// property. Only the superscript and subscript digits among them, such as
+/// `²` and `₀`, are alphanumeric, and so part of words by default. The others,
+/// such as `∂`, `∇`, `∞` and the superscript and subscript signs `⁺` and `₍`,
+/// are always separators.
+///
+/// ## Example:
+///
+/// ```
+/// use heck::{AsSnakeCase, MathSymbols, Options};
+///
+/// let options = Options::new().math_symbols(MathSymbols::Separate);
+/// assert_eq!(AsSnakeCase("AreaM²").to_string(), "area_m²");
+/// assert_eq!(AsSnakeCase("AreaM²").with_options(options).to_string(), "area_m");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum MathSymbols {
+    /// Keep superscript and subscript digits in words, like other digits.
+    Keep,
+    /// Treat superscript and subscript digits as separators, so that `x²y`
+    /// is `x|y`.
+    Separate,
+}
+
+impl Default for MathSymbols {
+    fn default() -> Self {
+        MathSymbols::Keep
+    }
+}
+
+/// How an apostrophe between two letters, as in `it's` or `rock'n'roll`, is
+/// treated.
+///
+/// Both the ASCII apostrophe `'` and the right single quotation mark `’` are
+/// apostrophes. An apostrophe which is not between two letters, such as the
+/// one ending the possessive `users'`, is always a separator. The modifier
+/// letter apostrophe `ʼ` is a letter, and so always part of a word.
+///
+/// An apostrophe which is part of a word never begins a new word after it, so
+/// with `Drop` and `Keep` the letters after it stay in the same word even if
+/// their case changes: `o'ClockTime` is `o'clock_time` in snake case.
+///
+/// ## Example:
+///
+/// ```
+/// use heck::{AsSnakeCase, ContractionPolicy, Options};
+///
+/// let convert = |policy| {
+///     let options = Options::new().contractions(policy);
+///     AsSnakeCase("It's done").with_options(options).to_string()
+/// };
+/// assert_eq!(convert(ContractionPolicy::Split), "it_s_done");
+/// assert_eq!(convert(ContractionPolicy::Drop), "its_done");
+/// assert_eq!(convert(ContractionPolicy::Keep), "it's_done");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ContractionPolicy {
+    /// The apostrophe is a separator like any other, so `it's` is `it|s`.
+    Split,
+    /// The apostrophe is part of the word, but is not written, so `it's`
+    /// becomes `its`.
+    Drop,
+    /// The apostrophe is part of the word, and is written, so `it's` stays
+    /// `it's`.
+    Keep,
+}
+
+impl Default for ContractionPolicy {
+    fn default() -> Self {
+        ContractionPolicy::Split
+    }
+}
+
+/// This wrapper performs a case conversion configured by [`Options`] in
+/// [`core::fmt::Display`].
+///
+/// It is created with the `with_options` method of a case conversion wrapper,
+/// such as [`AsUpperCamelCase::with_options`](crate::AsUpperCamelCase::with_options).
+#[derive(Clone, Copy)]
+pub struct WithOptions<'a, C> {
+    pub(crate) case: C,
+    pub(crate) options: Options<'a>,
+}
+
+impl<'a, C> From<WithOptions<'a, C>> for String
+where
+    WithOptions<'a, C>: fmt::Display,
+{
+    fn from(case: WithOptions<'a, C>) -> String {
+        case.to_string()
+    }
+}
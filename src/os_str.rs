@@ -0,0 +1,73 @@
+use core::str::{self, Utf8Error};
+use std::{
+    ffi::{OsStr, OsString},
+    os::unix::ffi::OsStrExt,
+};
+
+use crate::{ToKebabCase, ToSnakeCase};
+
+/// Convert an `OsStr` to snake case, replacing invalid UTF-8.
+///
+/// Invalid sequences are replaced with U+FFFD REPLACEMENT CHARACTER before
+/// converting. The replacement character is not a word character, so invalid
+/// sequences act as word boundaries and are dropped from the output. Use
+/// [`try_to_snake_case_os`] to reject invalid UTF-8 instead.
+///
+/// ## Example:
+///
+/// ```
+/// use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+///
+/// let name = OsStr::from_bytes(b"MyFile\xffName");
+/// assert_eq!(heck::to_snake_case_os_lossy(name), "my_file_name");
+/// ```
+pub fn to_snake_case_os_lossy(s: &OsStr) -> OsString {
+    s.to_string_lossy().to_snake_case().into()
+}
+
+/// Convert an `OsStr` to snake case, or return an error if it is not valid
+/// UTF-8.
+pub fn try_to_snake_case_os(s: &OsStr) -> Result<OsString, Utf8Error> {
+    Ok(str::from_utf8(s.as_bytes())?.to_snake_case().into())
+}
+
+/// Convert an `OsStr` to kebab case, replacing invalid UTF-8.
+///
+/// Invalid sequences act as word boundaries, as in
+/// [`to_snake_case_os_lossy`].
+pub fn to_kebab_case_os_lossy(s: &OsStr) -> OsString {
+    s.to_string_lossy().to_kebab_case().into()
+}
+
+/// Convert an `OsStr` to kebab case, or return an error if it is not valid
+/// UTF-8.
+pub fn try_to_kebab_case_os(s: &OsStr) -> Result<OsString, Utf8Error> {
+    Ok(str::from_utf8(s.as_bytes())?.to_kebab_case().into())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+    use super::{
+        to_kebab_case_os_lossy, to_snake_case_os_lossy, try_to_kebab_case_os, try_to_snake_case_os,
+    };
+
+    #[test]
+    fn valid() {
+        let name = OsStr::new("MyFile Name.txt");
+        assert_eq!(to_snake_case_os_lossy(name), "my_file_name_txt");
+        assert_eq!(try_to_snake_case_os(name).unwrap(), "my_file_name_txt");
+        assert_eq!(to_kebab_case_os_lossy(name), "my-file-name-txt");
+        assert_eq!(try_to_kebab_case_os(name).unwrap(), "my-file-name-txt");
+    }
+
+    #[test]
+    fn invalid() {
+        let name = OsStr::from_bytes(b"MyFile\xff\xfeName");
+        assert_eq!(to_snake_case_os_lossy(name), "my_file_name");
+        assert_eq!(to_kebab_case_os_lossy(name), "my-file-name");
+        assert_eq!(try_to_snake_case_os(name).unwrap_err().valid_up_to(), 6);
+        assert_eq!(try_to_kebab_case_os(name).unwrap_err().valid_up_to(), 6);
+    }
+}
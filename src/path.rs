@@ -0,0 +1,122 @@
+use alloc::string::String;
+
+use crate::split_extension;
+
+/// Convert each segment of a file path with `convert`, keeping the path
+/// separators and the extension of the last segment.
+///
+/// The path is split at `/`, and on Windows also at `\`. Each segment is
+/// converted separately, so every segment of a path in camel case begins as
+/// the first word would. Empty segments and the segments `.` and `..` are
+/// kept as they are, and so are leading dots, so that `.github` stays a
+/// dotfile. An extension, as recognized by [`Options::preserve_extension`], is
+/// split off the last segment before converting it, and lowercased as that
+/// option does.
+///
+/// [`Options::preserve_extension`]: crate::Options::preserve_extension
+///
+/// ## Example:
+///
+/// ```
+/// use heck::{convert_path, ToSnakeCase};
+///
+/// let path = convert_path("src/MyModule/FooBar.rs", |s| s.to_snake_case());
+/// assert_eq!(path, "src/my_module/foo_bar.rs");
+/// ```
+pub fn convert_path<F>(path: &str, mut convert: F) -> String
+where
+    F: FnMut(&str) -> String,
+{
+    let mut out = String::with_capacity(path.len());
+    let mut rest = path;
+
+    loop {
+        let (segment, separator) = match rest.find(is_separator) {
+            Some(i) => (&rest[..i], Some(&rest[i..i + 1])),
+            None => (rest, None),
+        };
+
+        if segment == "." || segment == ".." {
+            out.push_str(segment);
+        } else {
+            let stem = segment.trim_start_matches('.');
+            out.push_str(&segment[..segment.len() - stem.len()]);
+
+            let (stem, extension) = match separator {
+                Some(_) => (stem, None),
+                None => split_extension(stem),
+            };
+            if !stem.is_empty() {
+                out.push_str(&convert(stem));
+            }
+            if let Some(extension) = extension {
+                out.push('.');
+                out.extend(extension.chars().map(|c| c.to_ascii_lowercase()));
+            }
+        }
+
+        match separator {
+            Some(separator) => {
+                out.push_str(separator);
+                rest = &rest[segment.len() + 1..];
+            }
+            None => return out,
+        }
+    }
+}
+
+/// Whether `c` separates the segments of a path.
+fn is_separator(c: char) -> bool {
+    c == '/' || cfg!(windows) && c == '\\'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::convert_path;
+    use crate::{ToKebabCase, ToLowerCamelCase, ToSnakeCase, ToUpperCamelCase};
+
+    #[test]
+    fn nested_paths() {
+        let snake = |s: &str| convert_path(s, |s| s.to_snake_case());
+
+        assert_eq!(snake("src/MyModule/FooBar.rs"), "src/my_module/foo_bar.rs");
+        assert_eq!(
+            snake("/usr/LocalLib/HTTPServer.tar"),
+            "/usr/local_lib/http_server.tar"
+        );
+        assert_eq!(
+            snake("../Parent Dir/./MyFile.TXT"),
+            "../parent_dir/./my_file.txt"
+        );
+        assert_eq!(snake("Some.Dir/FileName"), "some_dir/file_name");
+        assert_eq!(
+            snake("docs/.GitHub/.EditorConfig"),
+            "docs/.git_hub/.editor_config"
+        );
+        assert_eq!(snake("a//b/"), "a//b/");
+        assert_eq!(snake(""), "");
+        assert_eq!(snake("FooBar.rs"), "foo_bar.rs");
+
+        let kebab = |s: &str| convert_path(s, |s| s.to_kebab_case());
+        assert_eq!(
+            kebab("assets/MyIcons/BigLogo.svg"),
+            "assets/my-icons/big-logo.svg"
+        );
+
+        let camel = |s: &str| convert_path(s, |s| s.to_lower_camel_case());
+        assert_eq!(camel("my_module/foo_bar.ts"), "myModule/fooBar.ts");
+
+        let pascal = |s: &str| convert_path(s, |s| s.to_upper_camel_case());
+        assert_eq!(pascal("my_module/foo_bar.cs"), "MyModule/FooBar.cs");
+    }
+
+    #[test]
+    fn backslashes() {
+        let snake = convert_path("MyDir\\FooBar.rs", |s| s.to_snake_case());
+        if cfg!(windows) {
+            assert_eq!(snake, "my_dir\\foo_bar.rs");
+        } else {
+            assert_eq!(snake, "my_dir_foo_bar.rs");
+        }
+    }
+}
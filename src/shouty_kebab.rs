@@ -1,8 +1,11 @@
 use core::fmt;
 
-use alloc::{borrow::ToOwned, string::ToString};
+use alloc::{
+    borrow::ToOwned,
+    string::{String, ToString},
+};
 
-use crate::{transform, uppercase};
+use crate::{transform, uppercase, Options, WithOptions};
 
 /// This trait defines a shouty kebab case conversion.
 ///
@@ -38,17 +41,51 @@ impl ToShoutyKebabCase for str {
 /// let sentence = "We are going to inherit the earth.";
 /// assert_eq!(format!("{}", AsShoutyKebabCase(sentence)), "WE-ARE-GOING-TO-INHERIT-THE-EARTH");
 /// ```
+#[derive(Clone, Copy)]
 pub struct AsShoutyKebabCase<T: AsRef<str>>(pub T);
 
+impl<T: AsRef<str>> AsShoutyKebabCase<T> {
+    /// Configure this conversion with the given [`Options`].
+    pub fn with_options(self, options: Options) -> WithOptions<Self> {
+        WithOptions {
+            case: self,
+            options,
+        }
+    }
+}
+
 impl<T: AsRef<str>> fmt::Display for AsShoutyKebabCase<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        transform(self.0.as_ref(), uppercase, |f| write!(f, "-"), f)
+        fmt::Display::fmt(
+            &AsShoutyKebabCase(self.0.as_ref()).with_options(Options::new()),
+            f,
+        )
+    }
+}
+
+impl<T: AsRef<str>> From<AsShoutyKebabCase<T>> for String {
+    fn from(case: AsShoutyKebabCase<T>) -> String {
+        case.to_string()
+    }
+}
+
+impl<T: AsRef<str>> fmt::Display for WithOptions<'_, AsShoutyKebabCase<T>> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        transform(
+            self.case.0.as_ref(),
+            self.options,
+            uppercase,
+            |_, f| write!(f, "{}", self.options.separator.unwrap_or('-')),
+            f,
+        )
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::ToShoutyKebabCase;
+    use super::{AsShoutyKebabCase, ToShoutyKebabCase};
+    use crate::Options;
+    use alloc::string::ToString;
 
     macro_rules! t {
         ($t:ident : $s1:expr => $s2:expr) => {
@@ -70,4 +107,36 @@ mod tests {
     t!(test9: "XΣXΣ baﬄe" => "XΣXΣ-BAFFLE");
     t!(test10: "XMLHttpRequest" => "XML-HTTP-REQUEST");
     t!(test11: "SHOUTY-KEBAB-CASE" => "SHOUTY-KEBAB-CASE");
+
+    t!(test12: "XML-HTTP-REQUEST" => "XML-HTTP-REQUEST");
+    t!(test13: "Cafe\u{301} Menu" => "CAFE\u{301}-MENU");
+    t!(test14: "KĸA" => "KĸA");
+
+    #[test]
+    fn idempotent() {
+        for s in [
+            "XMLHttpRequest",
+            "XMLHTTPRequest",
+            "ABC123dEEf456FOO",
+            "MixedUP CamelCase, with some Spaces",
+            "ΟΔΟΣΟδος",
+            "ß straße",
+            "ǅungla ǰ ΐ",
+            "1st place",
+            "v2API",
+        ] {
+            let once = s.to_shouty_kebab_case();
+            assert_eq!(once.to_shouty_kebab_case(), once, "{}", s);
+        }
+    }
+
+    #[test]
+    fn number_starts_word() {
+        let options = Options::new().number_starts_word(true);
+        let convert = |s: &str| AsShoutyKebabCase(s).with_options(options).to_string();
+
+        assert_eq!("md5Sum".to_shouty_kebab_case(), "MD5-SUM");
+        assert_eq!(convert("md5Sum"), "MD-5-SUM");
+        assert_eq!(convert("base64Encode"), "BASE-64-ENCODE");
+    }
 }
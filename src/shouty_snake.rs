@@ -1,8 +1,11 @@
 use core::fmt;
 
-use alloc::{borrow::ToOwned, string::ToString};
+use alloc::{
+    borrow::ToOwned,
+    string::{String, ToString},
+};
 
-use crate::{transform, uppercase};
+use crate::{transform, uppercase, Options, WithOptions};
 
 /// This trait defines a shouty snake case conversion.
 ///
@@ -52,23 +55,64 @@ impl ToShoutySnakeCase for str {
 /// let sentence = "That world is growing in this minute.";
 /// assert_eq!(format!("{}", AsShoutySnakeCase(sentence)), "THAT_WORLD_IS_GROWING_IN_THIS_MINUTE");
 /// ```
+#[derive(Clone, Copy)]
 pub struct AsShoutySnakeCase<T: AsRef<str>>(pub T);
 
+impl<T: AsRef<str>> AsShoutySnakeCase<T> {
+    /// Configure this conversion with the given [`Options`].
+    pub fn with_options(self, options: Options) -> WithOptions<Self> {
+        WithOptions {
+            case: self,
+            options,
+        }
+    }
+}
+
 impl<T: AsRef<str>> fmt::Display for AsShoutySnakeCase<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        transform(self.0.as_ref(), uppercase, |f| write!(f, "_"), f)
+        fmt::Display::fmt(
+            &AsShoutySnakeCase(self.0.as_ref()).with_options(Options::new()),
+            f,
+        )
+    }
+}
+
+impl<T: AsRef<str>> From<AsShoutySnakeCase<T>> for String {
+    fn from(case: AsShoutySnakeCase<T>) -> String {
+        case.to_string()
+    }
+}
+
+impl<T: AsRef<str>> fmt::Display for WithOptions<'_, AsShoutySnakeCase<T>> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        transform(
+            self.case.0.as_ref(),
+            self.options,
+            uppercase,
+            |_, f| write!(f, "{}", self.options.separator.unwrap_or('_')),
+            f,
+        )
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::ToShoutySnakeCase;
+    use super::{AsShoutySnakeCase, ToShoutySnakeCase};
+    use crate::Options;
+    use alloc::string::ToString;
 
     macro_rules! t {
-        ($t:ident : $s1:expr => $s2:expr) => {
+        ($t:ident : $($s1:expr => $s2:expr),+ $(,)?) => {
             #[test]
             fn $t() {
-                assert_eq!($s1.to_shouty_snake_case(), $s2)
+                $(assert_eq!($s1.to_shouty_snake_case(), $s2);)+
+            }
+        };
+        ($t:ident : $options:expr ; $($s1:expr => $s2:expr),+ $(,)?) => {
+            #[test]
+            fn $t() {
+                let options = $options;
+                $(assert_eq!(AsShoutySnakeCase($s1).with_options(options).to_string(), $s2);)+
             }
         };
     }
@@ -83,4 +127,44 @@ mod tests {
     t!(test8: "this-contains_ ALLKinds OfWord_Boundaries" => "THIS_CONTAINS_ALL_KINDS_OF_WORD_BOUNDARIES");
     t!(test9: "XΣXΣ baﬄe" => "XΣXΣ_BAFFLE");
     t!(test10: "XMLHttpRequest" => "XML_HTTP_REQUEST");
+
+    t!(test11: "XML_HTTP_REQUEST" => "XML_HTTP_REQUEST");
+    t!(test12: "Cafe\u{301} Menu" => "CAFE\u{301}_MENU");
+    t!(test13: "KĸA" => "KĸA");
+
+    #[test]
+    fn idempotent() {
+        for s in [
+            "XMLHttpRequest",
+            "XMLHTTPRequest",
+            "ABC123dEEf456FOO",
+            "MixedUP CamelCase, with some Spaces",
+            "ΟΔΟΣΟδος",
+            "ß straße",
+            "ǅungla ǰ ΐ",
+            "1st place",
+            "v2API",
+        ] {
+            let once = s.to_shouty_snake_case();
+            assert_eq!(once.to_shouty_snake_case(), once, "{}", s);
+        }
+    }
+
+    t!(number_starts_word: Options::new().number_starts_word(true);
+        "sha256Hash" => "SHA_256_HASH",
+        "md5Sum" => "MD_5_SUM",
+        "base64Encode" => "BASE_64_ENCODE",
+        "SHA256Hash" => "SHA_256_HASH",
+        "sha256hash" => "SHA_256HASH",
+        "1st Place" => "1ST_PLACE",
+        "SHA_256_HASH" => "SHA_256_HASH",
+        "SHA256_HASH" => "SHA_256_HASH",
+    );
+
+    t!(number_starts_word_default:
+        "sha256Hash" => "SHA256_HASH",
+        "md5Sum" => "MD5_SUM",
+        "base64Encode" => "BASE64_ENCODE",
+        "SHA256_HASH" => "SHA256_HASH",
+    );
 }
@@ -1,10 +1,12 @@
+use core::ops::Deref;
+
 use alloc::{
     borrow::ToOwned,
     fmt,
     string::{String, ToString},
 };
 
-use crate::{lowercase, transform};
+use crate::{is_combining_diacritic, lowercase, transform, Options, WithOptions};
 
 /// This trait defines a snake case conversion.
 ///
@@ -21,6 +23,15 @@ use crate::{lowercase, transform};
 pub trait ToSnakeCase: ToOwned {
     /// Convert this type to snake case.
     fn to_snake_case(&self) -> Self::Owned;
+
+    /// Convert this type to snake case, returning a [`SnakeCased`] string to
+    /// show that it has been converted.
+    fn to_snake_cased(&self) -> SnakeCased
+    where
+        Self: AsRef<str>,
+    {
+        SnakeCased(AsSnakeCase(self).to_string())
+    }
 }
 
 /// Oh heck, `SnekCase` is an alias for [`ToSnakeCase`]. See ToSnakeCase for
@@ -52,23 +63,312 @@ impl ToSnakeCase for str {
 /// let sentence = "We carry a new world here, in our hearts.";
 /// assert_eq!(format!("{}", AsSnakeCase(sentence)), "we_carry_a_new_world_here_in_our_hearts");
 /// ```
+#[derive(Clone, Copy)]
 pub struct AsSnakeCase<T: AsRef<str>>(pub T);
 
+impl<T: AsRef<str>> AsSnakeCase<T> {
+    /// Configure this conversion with the given [`Options`].
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use heck::{AsSnakeCase, Options};
+    ///
+    /// let options = Options::new().keep_namespace_separators(true);
+    /// assert_eq!(format!("{}", AsSnakeCase("MyModule::SomeType").with_options(options)), "my_module::some_type");
+    /// ```
+    pub fn with_options(self, options: Options) -> WithOptions<Self> {
+        WithOptions {
+            case: self,
+            options,
+        }
+    }
+}
+
 impl<T: AsRef<str>> fmt::Display for AsSnakeCase<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        transform(self.0.as_ref(), lowercase, |f| write!(f, "_"), f)
+        fmt::Display::fmt(
+            &AsSnakeCase(self.0.as_ref()).with_options(Options::new()),
+            f,
+        )
+    }
+}
+
+impl<T: AsRef<str>> From<AsSnakeCase<T>> for String {
+    fn from(case: AsSnakeCase<T>) -> String {
+        case.to_string()
+    }
+}
+
+impl<T: AsRef<str>> fmt::Display for WithOptions<'_, AsSnakeCase<T>> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let options = self.options;
+        transform(
+            self.case.0.as_ref(),
+            options,
+            |s, f| lowercase(s, options, f),
+            |separator, f| {
+                if options.keep_namespace_separators && separator.contains("::") {
+                    write!(f, "::")
+                } else {
+                    write!(f, "{}", options.separator.unwrap_or('_'))
+                }
+            },
+            f,
+        )
+    }
+}
+
+/// A string which has been converted to snake case.
+///
+/// It is returned by [`ToSnakeCase::to_snake_cased`], and can only be created
+/// by converting a string, so an API can take it to require snake case input.
+/// It dereferences to `str`, and compares equal to strings with the same
+/// contents.
+///
+/// ## Example:
+///
+/// ```
+/// use heck::ToSnakeCase;
+///
+/// let name = "MyTable".to_snake_cased();
+/// assert_eq!(name, "my_table");
+/// assert!(name.starts_with("my_"));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SnakeCased(String);
+
+impl SnakeCased {
+    /// Return the snake case string.
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl Deref for SnakeCased {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for SnakeCased {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for SnakeCased {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<SnakeCased> for String {
+    fn from(snake: SnakeCased) -> String {
+        snake.0
+    }
+}
+
+impl PartialEq<str> for SnakeCased {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for SnakeCased {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialEq<SnakeCased> for str {
+    fn eq(&self, other: &SnakeCased) -> bool {
+        self == other.0
+    }
+}
+
+impl PartialEq<SnakeCased> for &str {
+    fn eq(&self, other: &SnakeCased) -> bool {
+        *self == other.0
+    }
+}
+
+/// This type converts text which arrives in chunks to snake case.
+///
+/// Words can be split across chunks, so only the text up to the last
+/// separator seen so far is converted; the rest is kept until more text
+/// arrives or the stream is finished. The output is the same as converting
+/// the whole text at once.
+///
+/// ## Example:
+///
+/// ```
+/// use heck::SnakeCaseStream;
+///
+/// let mut stream = SnakeCaseStream::new();
+/// stream.push("We carry a new Wo");
+/// stream.push("rld here, in ou");
+/// stream.push("rHearts.");
+/// assert_eq!(stream.finish(), "we_carry_a_new_world_here_in_our_hearts");
+/// ```
+#[derive(Debug, Default)]
+pub struct SnakeCaseStream {
+    output: String,
+    /// The text after the last separator, which may be continued by the next
+    /// chunk.
+    pending: String,
+}
+
+impl SnakeCaseStream {
+    /// Create an empty stream.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the next chunk of text to the stream.
+    pub fn push(&mut self, chunk: &str) {
+        // The pending text has no separator, so only the new chunk is searched
+        // for one. Combining marks and zero width non-joiners may continue the
+        // word before them, so they are never taken as the end of the text to
+        // convert.
+        let start = self.pending.len();
+        self.pending.push_str(chunk);
+
+        let complete =
+            match chunk.char_indices().rev().find(|&(_, c)| {
+                !c.is_alphanumeric() && !is_combining_diacritic(c) && c != '\u{200C}'
+            }) {
+                Some((i, c)) => start + i + c.len_utf8(),
+                None => return,
+            };
+
+        self.convert(complete);
+    }
+
+    /// Convert any remaining text, and return the snake case text.
+    pub fn finish(mut self) -> String {
+        self.convert(self.pending.len());
+        self.output
+    }
+
+    /// Convert `self.pending[..end]`, which must end with a separator or at the
+    /// end of the input, and append it to the output.
+    fn convert(&mut self, end: usize) {
+        let converted = self.pending[..end].to_snake_case();
+        if !converted.is_empty() {
+            if !self.output.is_empty() {
+                self.output.push('_');
+            }
+            self.output.push_str(&converted);
+        }
+        self.pending.drain(..end);
+    }
+}
+
+/// Convert a string to snake case, and check that it contains only lowercase
+/// ASCII letters, digits and underscores.
+///
+/// Letters and digits from any script are kept in words by the conversion,
+/// so this catches identifiers which a system limited to `[a-z0-9_]` would
+/// reject. Use [`to_snake_case_lossy`] to drop such characters instead.
+///
+/// ## Example:
+///
+/// ```
+/// use heck::{to_snake_case_strict, InvalidChar};
+///
+/// assert_eq!(to_snake_case_strict("MyTable2").unwrap(), "my_table2");
+/// assert_eq!(
+///     to_snake_case_strict("CaféMenu"),
+///     Err(InvalidChar { character: 'é', index: 3 })
+/// );
+/// ```
+pub fn to_snake_case_strict(s: &str) -> Result<String, InvalidChar> {
+    let snake = s.to_snake_case();
+    match snake.char_indices().find(|&(_, c)| !is_strict_char(c)) {
+        Some((index, character)) => Err(InvalidChar { character, index }),
+        None => Ok(snake),
+    }
+}
+
+/// Convert a string to snake case, dropping any characters other than
+/// lowercase ASCII letters, digits and underscores.
+///
+/// The characters are dropped from the snake case string, so they do not
+/// split words: `Naïve` becomes `nave`. A word which is left empty is dropped
+/// with the underscore before it.
+///
+/// ## Example:
+///
+/// ```
+/// use heck::to_snake_case_lossy;
+///
+/// assert_eq!(to_snake_case_lossy("CaféMenu"), "caf_menu");
+/// assert_eq!(to_snake_case_lossy("東京 Tower"), "tower");
+/// ```
+pub fn to_snake_case_lossy(s: &str) -> String {
+    let mut lossy = String::new();
+    for word in s.to_snake_case().split('_') {
+        let mut word = word.chars().filter(|&c| is_strict_char(c)).peekable();
+        if word.peek().is_some() {
+            if !lossy.is_empty() {
+                lossy.push('_');
+            }
+            lossy.extend(word);
+        }
+    }
+    lossy
+}
+
+/// Whether `c` is allowed in the output of [`to_snake_case_strict`].
+fn is_strict_char(c: char) -> bool {
+    c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_'
+}
+
+/// The error returned by [`to_snake_case_strict`] when the snake case string
+/// contains a character other than a lowercase ASCII letter, digit or
+/// underscore.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InvalidChar {
+    /// The first character which is not allowed.
+    pub character: char,
+    /// The byte index of the character in the snake case string.
+    pub index: usize,
+}
+
+impl fmt::Display for InvalidChar {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "invalid character {:?} at index {} in snake case identifier",
+            self.character, self.index
+        )
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::ToSnakeCase;
+    use super::{
+        to_snake_case_lossy, to_snake_case_strict, AsSnakeCase, InvalidChar, SnakeCaseStream,
+        SnakeCased, ToSnakeCase,
+    };
+    use crate::{ContractionPolicy, MathSymbols, Options};
+    use alloc::string::{String, ToString};
 
     macro_rules! t {
-        ($t:ident : $s1:expr => $s2:expr) => {
+        ($t:ident : $($s1:expr => $s2:expr),+ $(,)?) => {
             #[test]
             fn $t() {
-                assert_eq!($s1.to_snake_case(), $s2)
+                $(assert_eq!($s1.to_snake_case(), $s2);)+
+            }
+        };
+        ($t:ident : $options:expr ; $($s1:expr => $s2:expr),+ $(,)?) => {
+            #[test]
+            fn $t() {
+                let options = $options;
+                $(assert_eq!(AsSnakeCase($s1).with_options(options).to_string(), $s2);)+
             }
         };
     }
@@ -97,4 +397,433 @@ mod tests {
     t!(test23: "ABC123dEEf456FOO" => "abc123d_e_ef456_foo");
     t!(test24: "abcDEF" => "abc_def");
     t!(test25: "ABcDE" => "a_bc_de");
+    t!(test26: "HTTP2Server" => "http2_server");
+    t!(test27: "UTF8String" => "utf8_string");
+    t!(test28: "SHA256Hash" => "sha256_hash");
+    t!(test29: "Σ" => "σ");
+    t!(test30: "ΣΑΣ1" => "σας1");
+    t!(test31: "Α1Σ" => "α1σ");
+    t!(test32: "ΟΔΟΣΟδος" => "οδος_οδος");
+    t!(test33: "ΟΔΟΣ'ΟΔΟΣ" => "οδος_οδος");
+    t!(test34: "می\u{200C}رود" => "می\u{200C}رود");
+    t!(test35: "می\u{200C}رود به\u{200C}خانه" => "می\u{200C}رود_به\u{200C}خانه");
+    t!(test36: "Foo\u{200C} \u{200C}Bar\u{200C}" => "foo_bar");
+    // Unassigned code points and noncharacters are not alphanumeric, so they
+    // are separators.
+    t!(test37: "foo\u{378}bar" => "foo_bar");
+    t!(test38: "FooBar\u{FDD0}Baz\u{FFFF}" => "foo_bar_baz");
+    t!(test39: "\u{E0080}x\u{10FFFF}" => "x");
+    t!(test40: "ΑΣ\u{301}Α" => "ασ\u{301}α");
+    t!(test41: "ΟΔΟΣ\u{301}" => "οδος\u{301}");
+    t!(test42: "Α\u{301}Σ" => "α\u{301}ς");
+
+    t!(keep_namespace_separators: Options::new().keep_namespace_separators(true);
+        "MyModule::SomeType" => "my_module::some_type",
+        "std::collections::HashMap" => "std::collections::hash_map",
+        "::Foo::BarBaz::" => "foo::bar_baz",
+        "Foo:Bar" => "foo_bar",
+    );
+
+    t!(keep_namespace_separators_default: "MyModule::SomeType" => "my_module_some_type");
+
+    #[cfg(feature = "unicode-normalization")]
+    t!(ascii_fold: Options::new().ascii_fold(true);
+        "Café_Menu" => "cafe_menu",
+        "Cafe\u{301} Menu" => "cafe_menu",
+        "ÀÉÎÕÜ çãñ" => "aeiou_can",
+        "Crème Brûlée" => "creme_brulee",
+        "Øre Straße" => "øre_straße",
+        "Ёлка 한국어" => "ёлка_한국어",
+    );
+
+    #[cfg(feature = "unicode-normalization")]
+    t!(ascii_fold_default: "Café_Menu" => "café_menu");
+
+    t!(keep_middle_dot: Options::new().keep_middle_dot(true);
+        "Paral·lel" => "paral·lel",
+        "Intel·ligència Artificial" => "intel·ligència_artificial",
+        "COL·LECCIÓ" => "col·lecció",
+        "dot · separated·" => "dot_separated",
+    );
+
+    t!(keep_middle_dot_default:
+        "Paral·lel" => "paral_lel",
+        "Intel·ligència Artificial" => "intel_ligència_artificial",
+    );
+
+    t!(preserve_extension: Options::new().preserve_extension(true);
+        "MyFile.JSON" => "my_file.json",
+        "MyCoolFile.TXT" => "my_cool_file.txt",
+        "archive.tar.gz" => "archive_tar.gz",
+        "Some Notes.md" => "some_notes.md",
+        "README" => "readme",
+        ".bashrc" => "bashrc",
+        "Version1.0" => "version1_0",
+        "file.extension" => "file_extension",
+    );
+
+    t!(preserve_extension_default: "MyFile.JSON" => "my_file_json");
+
+    t!(preserve_extension_no_words: Options::new().preserve_extension(true).max_words(0);
+        "MyFile.txt" => "",
+        "___.txt" => "",
+    );
+
+    t!(script_boundary: Options::new().script_boundary(true);
+        "ファイルURL" => "ファイル_url",
+        "URLファイル" => "url_ファイル",
+        "ファイルを読み込みReadFile" => "ファイルを読み込み_read_file",
+        "用户ID2" => "用户_id2",
+        "한국어Text" => "한국어_text",
+        "祝你一天过得愉快" => "祝你一天过得愉快",
+        "v2版本" => "v2_版本",
+    );
+
+    t!(script_boundary_default: "ファイルURL" => "ファイルurl");
+
+    t!(merge_single_letter_words: Options::new().merge_single_letter_words(true);
+        "ABC123dEEf456FOO" => "abc123d_eef456_foo",
+        "AString" => "astring",
+        "getX" => "get_x",
+        "getXAxis" => "get_xaxis",
+        "x y" => "x_y",
+    );
+
+    t!(keep_dotted_numbers: Options::new().keep_dotted_numbers(true);
+        "Parser2.0Engine" => "parser2.0_engine",
+        "parser2.0engine" => "parser2.0engine",
+        "Release 1.2.3" => "release_1.2.3",
+        "v10.4.1-beta" => "v10.4.1_beta",
+        "1.x" => "1_x",
+        "end.2" => "end_2",
+        "2..0" => "2_0",
+        "3.14." => "3.14",
+    );
+
+    t!(keep_dotted_numbers_default: "Parser2.0Engine" => "parser2_0engine");
+
+    t!(acronym_map: Options::new().acronym_map(&[("ios", "iOS")]);
+        "IosVersion" => "iOS_version",
+    );
+
+    #[test]
+    fn stream() {
+        for s in [
+            "We carry a new world here, in our hearts.",
+            "MixedUP CamelCase, with some Spaces",
+            "this-contains_ ALLKinds OfWord_Boundaries",
+            "XΣXΣ baﬄe ΟΔΟΣ'ΟΔΟΣ",
+            "ABC123dEEf456FOO",
+            "__a__",
+            "می\u{200C}رود به\u{200C}خانه",
+            "",
+        ] {
+            let mut whole = SnakeCaseStream::new();
+            whole.push(s);
+            assert_eq!(whole.finish(), s.to_snake_case(), "{}", s);
+
+            let mut chars = SnakeCaseStream::new();
+            let mut buf = [0; 4];
+            for c in s.chars() {
+                chars.push(c.encode_utf8(&mut buf));
+            }
+            assert_eq!(chars.finish(), s.to_snake_case(), "{}", s);
+
+            for split in (0..=s.len()).filter(|&i| s.is_char_boundary(i)) {
+                let mut halves = SnakeCaseStream::new();
+                halves.push(&s[..split]);
+                halves.push("");
+                halves.push(&s[split..]);
+                assert_eq!(halves.finish(), s.to_snake_case(), "{} at {}", s, split);
+            }
+        }
+    }
+
+    // Each push only looks at the new text, so pushing a long word one
+    // character at a time takes linear time.
+    #[test]
+    fn stream_many_small_pushes() {
+        let s = "fooBar".repeat(1 << 15);
+        let mut stream = SnakeCaseStream::new();
+        let mut buf = [0; 4];
+        for c in s.chars() {
+            stream.push(c.encode_utf8(&mut buf));
+        }
+        assert_eq!(stream.finish(), s.to_snake_case());
+    }
+
+    t!(separator: Options::new().separator('.');
+        "MyConfigKey" => "my.config.key",
+    );
+
+    t!(separator_with_namespaces: Options::new().separator('.').keep_namespace_separators(true);
+        "my::ConfigKey" => "my::config.key",
+    );
+
+    #[test]
+    fn into_string() {
+        let case = AsSnakeCase("We carry a new world");
+        let expected = case.to_string();
+        assert_eq!(String::from(case), expected);
+
+        let options = Options::new().keep_namespace_separators(true);
+        let converted: String = AsSnakeCase("MyModule::SomeType")
+            .with_options(options)
+            .into();
+        assert_eq!(converted, "my_module::some_type");
+    }
+
+    t!(group_consecutive_capitals: Options::new().group_consecutive_capitals(true);
+        "ABC123dEEf456FOO" => "abc123d_ee_f456_foo",
+        "dEEf" => "d_ee_f",
+        "ABC123DEf456" => "abc123d_ef456",
+        "abc123DEf456" => "abc123_de_f456",
+        "ABcDE" => "ab_c_de",
+        "XMLHttpRequest" => "xml_http_request",
+        "IOError" => "io_error",
+        "IOStream" => "io_stream",
+        "CamelCase" => "camel_case",
+        "A1Bc" => "a1b_c",
+        "A1BCd" => "a1b_cd",
+    );
+
+    t!(brands: Options::new().brands(&["PostgreSQL", "macOS", "OpenGL", "GitHub"]);
+        "postgresqlQuery" => "PostgreSQL_query",
+        "PostgreSQLQuery" => "PostgreSQL_query",
+        "MyPostgreSQLDriver" => "my_PostgreSQL_driver",
+        "macOSVersion" => "macOS_version",
+        "OPENGL_RENDERER" => "OpenGL_renderer",
+        "github-actions" => "GitHub_actions",
+        "open_gl" => "open_gl",
+        "Postgre SQL" => "postgre_sql",
+        "postgresqlx" => "postgresqlx",
+    );
+
+    t!(brands_default: "PostgreSQLQuery" => "postgre_sql_query");
+
+    t!(emoji_words: Options::new().emoji_words(true);
+        "happy😀face" => "happy_😀_face",
+        "HappyFace😀" => "happy_face_😀",
+        "😀😀" => "😀_😀",
+        "👩\u{200D}💻 coder" => "👩\u{200D}💻_coder",
+        "wave👋🏽Hello" => "wave_👋🏽_hello",
+        "made in 🇯🇵🇫🇷" => "made_in_🇯🇵_🇫🇷",
+        "❤\u{FE0F}love" => "❤\u{FE0F}_love",
+        "end\u{200D}" => "end",
+    );
+
+    t!(emoji_words_default: "happy😀face" => "happy_face");
+
+    // The prefix is stripped before folding, and words are counted after
+    // folding has joined "Café" back into one word.
+    #[cfg(feature = "unicode-normalization")]
+    t!(options_order: Options::new().max_words(2).ascii_fold(true).strip_prefix("lib");
+        "libCafe\u{301}MenuItems" => "cafe_menu",
+        "lïbCaféMenu" => "lib_cafe",
+        "CaféMenuItems" => "cafe_menu",
+    );
+
+    t!(split_every_capital: Options::new().split_every_capital(true);
+        "XMLParser" => "x_m_l_parser",
+        "camelCase" => "camel_case",
+        "XMLHttpRequest" => "x_m_l_http_request",
+        "A1B2" => "a1_b2",
+        "A\u{301}B" => "a\u{301}_b",
+        "snake_case" => "snake_case",
+    );
+
+    t!(split_every_capital_default: "XMLParser" => "xml_parser");
+
+    t!(split_every_capital_grouped:
+        Options::new().split_every_capital(true).group_consecutive_capitals(true);
+        "dEEf" => "d_e_ef",
+    );
+
+    t!(math_symbols: Options::new().math_symbols(MathSymbols::Separate);
+        "AreaM²" => "area_m",
+        "x²y³z¹" => "x_y_z",
+        "H₂O" => "h_o",
+        "e⁰ x⁴⁵⁶⁷⁸⁹ a₀₁₂₃₄₅₆₇₈₉" => "e_x_a",
+        "x2 ⁿ" => "x2_ⁿ",
+    );
+
+    t!(math_symbols_default:
+        "x²y³z¹" => "x²y³z¹",
+        "H₂O" => "h₂o",
+        "∂x∇y∞z⁺w₍v" => "x_y_z_w_v",
+    );
+
+    #[test]
+    fn contractions() {
+        let convert = |s: &str, policy| {
+            let options = Options::new().contractions(policy);
+            AsSnakeCase(s).with_options(options).to_string()
+        };
+
+        for (s, split, drop, keep) in [
+            ("it's", "it_s", "its", "it's"),
+            ("IT'S", "it_s", "its", "it's"),
+            ("don’t stop", "don_t_stop", "dont_stop", "don’t_stop"),
+            ("rock'n'roll", "rock_n_roll", "rocknroll", "rock'n'roll"),
+            ("users' data", "users_data", "users_data", "users_data"),
+            ("'tis", "tis", "tis", "tis"),
+            ("it''s", "it_s", "it_s", "it_s"),
+            ("rock'2'roll", "rock_2_roll", "rock_2_roll", "rock_2_roll"),
+            ("o'ClockTime", "o_clock_time", "oclock_time", "o'clock_time"),
+            ("IT'Sa", "it_sa", "itsa", "it'sa"),
+            ("maʼalahi", "maʼalahi", "maʼalahi", "maʼalahi"),
+        ] {
+            assert_eq!(convert(s, ContractionPolicy::Split), split, "{}", s);
+            assert_eq!(convert(s, ContractionPolicy::Drop), drop, "{}", s);
+            assert_eq!(convert(s, ContractionPolicy::Keep), keep, "{}", s);
+        }
+        assert_eq!(AsSnakeCase("it's").to_string(), "it_s");
+    }
+
+    #[test]
+    fn copy_wrapper() {
+        let case = AsSnakeCase("FooBar");
+        let copy = case;
+        assert_eq!(case.to_string(), copy.to_string());
+
+        let owned = AsSnakeCase(String::from("FooBar"));
+        assert_eq!(owned.clone().to_string(), owned.to_string());
+
+        let with_options = case.with_options(Options::new().separator('.'));
+        let copy = with_options;
+        assert_eq!(with_options.to_string(), "foo.bar");
+        assert_eq!(copy.to_string(), "foo.bar");
+    }
+
+    t!(protocol_tokens: Options::new().protocol_tokens(&["ipv4", "ipv6", "utf8", "sha256"]);
+        "ipv4Address" => "ipv4_address",
+        "ipV4Address" => "ipv4_address",
+        "IPv6Route" => "ipv6_route",
+        "IPV6" => "ipv6",
+        "UTF8String" => "utf8_string",
+        "Utf8String" => "utf8_string",
+        "shA256Hash" => "sha256_hash",
+        "ip_v4" => "ip_v4",
+        "ipV44" => "ip_v44",
+    );
+
+    t!(protocol_tokens_default:
+        "ipv4Address" => "ipv4_address",
+        "ipV4Address" => "ip_v4_address",
+        "IPv6Route" => "i_pv6_route",
+        "UTF8String" => "utf8_string",
+        "Sha256Hash" => "sha256_hash",
+    );
+
+    t!(digit_then_upper_boundary: Options::new().digit_then_upper_boundary(true);
+        "v2API" => "v2_api",
+        "a1B" => "a1_b",
+        "ABC2Def" => "abc2_def",
+        "ABC2DEF" => "abc2_def",
+        "A1B" => "a1_b",
+        "A12b" => "a12b",
+        "HTTP2Server" => "http2_server",
+    );
+
+    t!(digit_then_upper_boundary_default:
+        "v2API" => "v2_api",
+        "a1B" => "a1_b",
+        "ABC2Def" => "abc2_def",
+        "ABC2DEF" => "abc2def",
+        "A1B" => "a1b",
+    );
+
+    t!(digit_then_upper_boundary_grouped:
+        Options::new().digit_then_upper_boundary(true).group_consecutive_capitals(true);
+        "A1Bc" => "a1_bc",
+    );
+
+    t!(final_sigma: Options::new().final_sigma(false);
+        "ΟΔΟΣ" => "οδοσ",
+        "ΟΔΟΣ ΣΑΣ" => "οδοσ_σασ",
+        "οδος" => "οδος",
+    );
+
+    t!(final_sigma_default:
+        "ΟΔΟΣ" => "οδος",
+        "ΟΔΟΣ ΣΑΣ" => "οδος_σας",
+    );
+
+    t!(final_sigma_options_default: Options::default();
+        "ΟΔΟΣ" => "οδος",
+    );
+
+    t!(final_sigma_reenabled: Options::new().final_sigma(false).final_sigma(true);
+        "ΟΔΟΣ" => "οδος",
+    );
+
+    #[test]
+    fn strict() {
+        assert_eq!(to_snake_case_strict("HTTPServer2").unwrap(), "http_server2");
+        assert_eq!(to_snake_case_strict("").unwrap(), "");
+        assert_eq!(
+            to_snake_case_strict("Crème Brûlée"),
+            Err(InvalidChar {
+                character: 'è',
+                index: 2
+            })
+        );
+        assert_eq!(
+            to_snake_case_strict("user 東京"),
+            Err(InvalidChar {
+                character: '東',
+                index: 5
+            })
+        );
+        assert_eq!(
+            to_snake_case_strict("ΟΔΟΣ").unwrap_err().to_string(),
+            "invalid character 'ο' at index 0 in snake case identifier"
+        );
+    }
+
+    #[test]
+    fn lossy() {
+        assert_eq!(to_snake_case_lossy("HTTPServer2"), "http_server2");
+        assert_eq!(to_snake_case_lossy("Crème Brûlée"), "crme_brle");
+        assert_eq!(to_snake_case_lossy("user 東京 name"), "user_name");
+        assert_eq!(to_snake_case_lossy("東京"), "");
+        assert_eq!(to_snake_case_lossy("日本Tower"), "tower");
+        assert_eq!(to_snake_case_lossy("Naïve"), "nave");
+    }
+
+    #[test]
+    fn snake_cased() {
+        let name = "MyTable Name".to_snake_cased();
+        assert_eq!(name.len(), 13);
+        assert_eq!(&name[..8], "my_table");
+        assert_eq!(name, "my_table_name");
+        assert_eq!("my_table_name", name);
+        assert_eq!(*name, *"my_table_name");
+        assert_eq!(name.to_string(), "my_table_name");
+        assert_eq!(name.to_snake_cased(), name);
+        assert_eq!(String::from(name.clone()), name.into_string());
+
+        fn takes_snake(name: &SnakeCased) -> &str {
+            name
+        }
+        assert_eq!(takes_snake(&"fooBar".to_snake_cased()), "foo_bar");
+    }
+
+    t!(keep_leading_underscore: Options::new().keep_leading_underscore(true);
+        "_internalValue" => "_internal_value",
+        "__privateField" => "__private_field",
+        "___init___" => "___init",
+        "internalValue" => "internal_value",
+        " _internalValue" => "internal_value",
+        "_" => "_",
+        "_-_Foo" => "_foo",
+    );
+
+    t!(keep_leading_underscore_no_words:
+        Options::new().keep_leading_underscore(true).max_words(0);
+        "_internalValue" => "",
+        "_" => "",
+    );
+
+    t!(keep_leading_underscore_default: "_internalValue" => "internal_value");
 }
@@ -1,11 +1,17 @@
-use core::fmt;
+use core::{
+    cell::{Cell, RefCell},
+    fmt,
+};
 
 use alloc::{
     borrow::ToOwned,
     string::{String, ToString},
 };
 
-use crate::{capitalize, transform};
+use crate::{
+    capitalize, lowercase, options::eq_ignore_case, transform, uppercase, Options, WithOptions,
+    WordKind,
+};
 
 /// This trait defines a title case conversion.
 ///
@@ -41,23 +47,240 @@ impl ToTitleCase for str {
 /// let sentence = "We have always lived in slums and holes in the wall.";
 /// assert_eq!(format!("{}", AsTitleCase(sentence)), "We Have Always Lived In Slums And Holes In The Wall");
 /// ```
+#[derive(Clone, Copy)]
 pub struct AsTitleCase<T: AsRef<str>>(pub T);
 
+impl<T: AsRef<str>> AsTitleCase<T> {
+    /// Configure this conversion with the given [`Options`].
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use heck::{AsTitleCase, Options};
+    ///
+    /// let options = Options::new().roman_numerals(true);
+    /// assert_eq!(format!("{}", AsTitleCase("henry viii").with_options(options)), "Henry VIII");
+    /// ```
+    pub fn with_options(self, options: Options) -> WithOptions<Self> {
+        WithOptions {
+            case: self,
+            options,
+        }
+    }
+}
+
 impl<T: AsRef<str>> fmt::Display for AsTitleCase<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        transform(self.0.as_ref(), capitalize, |f| write!(f, " "), f)
+        fmt::Display::fmt(
+            &AsTitleCase(self.0.as_ref()).with_options(Options::new()),
+            f,
+        )
+    }
+}
+
+impl<T: AsRef<str>> From<AsTitleCase<T>> for String {
+    fn from(case: AsTitleCase<T>) -> String {
+        case.to_string()
+    }
+}
+
+impl<T: AsRef<str>> fmt::Display for WithOptions<'_, AsTitleCase<T>> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let options = self.options;
+        // The index of the current word, and of the last word, so that small
+        // words are capitalized when they are first or last.
+        let index = Cell::new(0);
+        let last = if options.small_words.is_empty() {
+            0
+        } else {
+            count_boundaries(self.case.0.as_ref(), options)
+        };
+        transform(
+            self.case.0.as_ref(),
+            options,
+            |s, f| {
+                let small = index.get() != 0 && index.get() != last && is_small_word(s, options);
+                if options.roman_numerals && is_roman_numeral(s) {
+                    uppercase(s, f)
+                } else if options.keep_all_caps && WordKind::of(s) == WordKind::Acronym {
+                    f.write_str(s)
+                } else if small {
+                    lowercase(s, options, f)
+                } else {
+                    capitalize(s, options, f)
+                }
+            },
+            |separator, f| {
+                index.set(index.get() + 1);
+                if options.keep_dashes && separator == "-" {
+                    write!(f, "-")
+                } else {
+                    write!(f, "{}", options.separator.unwrap_or(' '))
+                }
+            },
+            f,
+        )
+    }
+}
+
+/// Convert a string to words separated by spaces, capitalizing the words for
+/// which `capitalize_word` returns `true` and lowercasing the rest.
+///
+/// `capitalize_word` is called with each word, as it is in `s`, and its index.
+/// This is the general form of the cases which separate words with spaces:
+/// Title Case capitalizes every word, and sentence case only the first.
+///
+/// ## Example:
+///
+/// ```
+/// use heck::to_spaced_case_with;
+///
+/// let s = "the_quick_brown_fox_jumps_over_the_lazy_dog";
+/// assert_eq!(
+///     to_spaced_case_with(s, |_, index| index % 3 == 2),
+///     "the quick Brown fox jumps Over the lazy Dog"
+/// );
+/// assert_eq!(
+///     to_spaced_case_with("XMLHttpRequest", |_, index| index == 0),
+///     "Xml http request"
+/// );
+/// ```
+pub fn to_spaced_case_with<F>(s: &str, capitalize_word: F) -> String
+where
+    F: FnMut(&str, usize) -> bool,
+{
+    struct SpacedCase<'a, F> {
+        s: &'a str,
+        capitalize_word: RefCell<F>,
+    }
+
+    impl<F: FnMut(&str, usize) -> bool> fmt::Display for SpacedCase<'_, F> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            let mut capitalize_word = self.capitalize_word.borrow_mut();
+            let index = Cell::new(0);
+            transform(
+                self.s,
+                Options::new(),
+                |s, f| {
+                    if capitalize_word(s, index.get()) {
+                        capitalize(s, Options::new(), f)
+                    } else {
+                        lowercase(s, Options::new(), f)
+                    }
+                },
+                |_, f| {
+                    index.set(index.get() + 1);
+                    write!(f, " ")
+                },
+                f,
+            )
+        }
+    }
+
+    SpacedCase {
+        s,
+        capitalize_word: RefCell::new(capitalize_word),
+    }
+    .to_string()
+}
+
+/// Whether `s` is one of the small words of `options`, ignoring case.
+fn is_small_word(s: &str, options: Options) -> bool {
+    options
+        .small_words
+        .iter()
+        .any(|small| eq_ignore_case(small, s))
+}
+
+/// The number of boundaries between words in the title case of `s`, which is
+/// one less than the number of words.
+fn count_boundaries(s: &str, options: Options) -> usize {
+    struct Discard;
+
+    impl fmt::Write for Discard {
+        fn write_str(&mut self, _: &str) -> fmt::Result {
+            Ok(())
+        }
+    }
+
+    struct CountBoundaries<'a> {
+        s: &'a str,
+        options: Options<'a>,
+        count: &'a Cell<usize>,
+    }
+
+    impl fmt::Display for CountBoundaries<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            transform(
+                self.s,
+                self.options,
+                |_, _| Ok(()),
+                |_, _| {
+                    self.count.set(self.count.get() + 1);
+                    Ok(())
+                },
+                f,
+            )
+        }
     }
+
+    let count = Cell::new(0);
+    // Writing to `Discard` cannot fail.
+    let _ = fmt::write(
+        &mut Discard,
+        format_args!(
+            "{}",
+            CountBoundaries {
+                s,
+                // The trailing separator is not between two words.
+                options: options.trailing_separator(false),
+                count: &count,
+            }
+        ),
+    );
+    count.get()
+}
+
+/// Whether `s` is a roman numeral between 1 and 399 in canonical form, in any
+/// case.
+fn is_roman_numeral(s: &str) -> bool {
+    // The forms of each digit, longest first so that the first matching
+    // prefix is the whole digit.
+    const HUNDREDS: [&str; 3] = ["ccc", "cc", "c"];
+    const TENS: [&str; 9] = ["lxxx", "lxx", "xxx", "xc", "xl", "lx", "xx", "l", "x"];
+    const ONES: [&str; 9] = ["viii", "vii", "iii", "ix", "iv", "vi", "ii", "v", "i"];
+
+    let mut rest = s;
+    for digits in [&HUNDREDS[..], &TENS[..], &ONES[..]] {
+        if let Some(digit) = digits.iter().find(|digit| {
+            rest.get(..digit.len())
+                .map_or(false, |prefix| prefix.eq_ignore_ascii_case(digit))
+        }) {
+            rest = &rest[digit.len()..];
+        }
+    }
+
+    !s.is_empty() && rest.is_empty()
 }
 
 #[cfg(test)]
 mod tests {
-    use super::ToTitleCase;
+    use super::{is_roman_numeral, to_spaced_case_with, AsTitleCase, ToTitleCase};
+    use crate::{ContractionPolicy, Locale, Options};
+    use alloc::{string::ToString, vec::Vec};
 
     macro_rules! t {
-        ($t:ident : $s1:expr => $s2:expr) => {
+        ($t:ident : $($s1:expr => $s2:expr),+ $(,)?) => {
+            #[test]
+            fn $t() {
+                $(assert_eq!($s1.to_title_case(), $s2);)+
+            }
+        };
+        ($t:ident : $options:expr ; $($s1:expr => $s2:expr),+ $(,)?) => {
             #[test]
             fn $t() {
-                assert_eq!($s1.to_title_case(), $s2)
+                let options = $options;
+                $(assert_eq!(AsTitleCase($s1).with_options(options).to_string(), $s2);)+
             }
         };
     }
@@ -72,4 +295,178 @@ mod tests {
     t!(test8: "this-contains_ ALLKinds OfWord_Boundaries" => "This Contains All Kinds Of Word Boundaries");
     t!(test9: "XΣXΣ baﬄe" => "Xσxς Baﬄe");
     t!(test10: "XMLHttpRequest" => "Xml Http Request");
+    t!(test11: "123abc" => "123abc");
+    t!(test12: "123ABC def" => "123abc Def");
+    t!(test13: "room 101b" => "Room 101b");
+    t!(test14: "ΟΔΟΣ" => "Οδος");
+    t!(test15: "ΑΣ ΣΑ" => "Ας Σα");
+    // Words starting with a digit are not capitalized, so ordinal suffixes
+    // stay lowercase.
+    t!(test16: "1st place" => "1st Place");
+    t!(test17: "22ND_PLACE" => "22nd Place");
+    t!(test18: "3rd-party" => "3rd Party");
+    t!(test19: "the 4th of july" => "The 4th Of July");
+    // Digits never start a word or capitalize the letters after them. A word
+    // begins at a capital after a lowercase letter, as in `NamE`, whether or
+    // not digits follow it.
+    t!(test20: "FieldNamE11" => "Field Nam E11");
+    t!(test21: "v2Point0" => "V2 Point0");
+    t!(test22: "version2point0" => "Version2point0");
+    t!(test23: "A1b2C3" => "A1b2 C3");
+
+    t!(roman_numerals: Options::new().roman_numerals(true);
+        "henry viii" => "Henry VIII",
+        "louis_xiv" => "Louis XIV",
+        "world war ii" => "World War II",
+        "chapter XLII" => "Chapter XLII",
+        "vi via" => "VI Via",
+        "did mix it" => "Did Mix It",
+    );
+
+    t!(roman_numerals_default: "henry viii" => "Henry Viii");
+
+    #[test]
+    fn roman_numeral_forms() {
+        for numeral in [
+            "i", "iv", "ix", "xiv", "xix", "xl", "lxxxviii", "xc", "cccxcix",
+        ] {
+            assert!(is_roman_numeral(numeral), "{}", numeral);
+        }
+        for word in [
+            "", "iiii", "vv", "ic", "mix", "did", "dc", "civil", "via", "é",
+        ] {
+            assert!(!is_roman_numeral(word), "{}", word);
+        }
+    }
+
+    t!(keep_dashes: Options::new().keep_dashes(true);
+        "well-known" => "Well-Known",
+        "a state-of-the-art tool" => "A State-Of-The-Art Tool",
+        "self-driving_cars" => "Self-Driving Cars",
+        "one - two" => "One Two",
+        "one--two" => "One Two",
+        "one—two" => "One Two",
+        "-leading-" => "Leading",
+        "wellKnown-thing" => "Well Known-Thing",
+    );
+
+    t!(keep_dashes_default: "well-known" => "Well Known");
+
+    t!(dutch: Options::new().locale(Locale::Dutch);
+        "ijsland" => "IJsland",
+        "IJMUIDEN" => "IJmuiden",
+        "de ijssel" => "De IJssel",
+        "bijna" => "Bijna",
+        "ij" => "IJ",
+        "i" => "I",
+    );
+
+    t!(dutch_default: "ijsland" => "Ijsland");
+
+    t!(separator: Options::new().separator('\u{a0}').keep_dashes(true);
+        "well-known words" => "Well-Known\u{a0}Words",
+    );
+
+    t!(hyphenated_small_words: Options::new().keep_dashes(true).small_words(&["in", "of", "the"]);
+        "mother-in-law" => "Mother-in-Law",
+        "a state-of-the-art kitchen" => "A State-of-the-Art Kitchen",
+        "well-being" => "Well-Being",
+    );
+
+    t!(hyphenated_without_small_words: Options::new().keep_dashes(true);
+        "mother-in-law" => "Mother-In-Law",
+    );
+
+    t!(small_words: Options::new().small_words(&["a", "an", "the", "of", "in", "for", "to", "and"]);
+        "a tale of two cities" => "A Tale of Two Cities",
+        "what is it for" => "What Is It For",
+        "THE_LORD_OF_THE_RINGS" => "The Lord of the Rings",
+        "somethingToLiveFor" => "Something to Live For",
+        "of" => "Of",
+        "of the" => "Of The",
+        "  in and out  " => "In and Out",
+    );
+
+    t!(small_words_default: "a tale of two cities" => "A Tale Of Two Cities");
+
+    t!(small_words_hyphenated:
+        Options::new()
+            .small_words(&["a", "an", "the", "of", "in", "for", "to", "and"])
+            .keep_dashes(true);
+        "mother-in-law" => "Mother-in-Law",
+    );
+
+    t!(small_words_max_words:
+        Options::new()
+            .small_words(&["a", "an", "the", "of", "in", "for", "to", "and"])
+            .max_words(3);
+        "the end of the tale" => "The End Of",
+    );
+
+    t!(contractions_split: Options::new().contractions(ContractionPolicy::Split);
+        "it's DONE" => "It S Done",
+    );
+
+    t!(contractions_drop: Options::new().contractions(ContractionPolicy::Drop);
+        "it's DONE" => "Its Done",
+    );
+
+    t!(contractions_keep: Options::new().contractions(ContractionPolicy::Keep);
+        "it's DONE" => "It's Done",
+        "the users' data" => "The Users Data",
+    );
+
+    #[test]
+    fn spaced_case_with() {
+        let s = "XMLHttpRequest for_the ΣΑΣ";
+        assert_eq!(to_spaced_case_with(s, |_, _| true), s.to_title_case());
+        assert_eq!(
+            to_spaced_case_with(s, |_, _| false),
+            "xml http request for the σας"
+        );
+
+        let mut seen = Vec::new();
+        let spaced = to_spaced_case_with(s, |word, index| {
+            seen.push((word.to_string(), index));
+            word == "for"
+        });
+        assert_eq!(spaced, "xml http request For the σας");
+        assert_eq!(seen[2], ("Request".to_string(), 2));
+        assert_eq!(seen.len(), 6);
+        assert_eq!(to_spaced_case_with("", |_, _| true), "");
+    }
+
+    #[test]
+    fn keep_all_caps() {
+        let brands = ["iPhone", "Nasa"];
+        let options = Options::new().keep_all_caps(true);
+        let convert = |s: &str, options: Options| AsTitleCase(s).with_options(options).to_string();
+
+        assert_eq!(
+            convert("iPhone 15 pro by NASA", options),
+            "I Phone 15 Pro By NASA"
+        );
+        assert_eq!(
+            convert("iPhone 15 pro by NASA", options.brands(&brands[..1])),
+            "iPhone 15 Pro By NASA"
+        );
+        assert_eq!(
+            convert("IPHONE 15 pro by NASA", options.brands(&brands[..1])),
+            "iPhone 15 Pro By NASA"
+        );
+        assert_eq!(
+            convert("iPhone 15 pro by NASA", options.brands(&brands)),
+            "iPhone 15 Pro By Nasa"
+        );
+        assert_eq!(
+            convert("XMLHttpRequest A I", options),
+            "XML Http Request A I"
+        );
+        assert_eq!(convert("ABC123dEEf456FOO", options), "Abc123d E Ef456 FOO");
+        assert_eq!(
+            convert("IT'Sa test", options.contractions(ContractionPolicy::Keep)),
+            "It'sa Test"
+        );
+        assert_eq!(convert("NASA_launch", Options::new()), "Nasa Launch");
+    }
 }
@@ -1,8 +1,11 @@
 use core::fmt;
 
-use alloc::{borrow::ToOwned, string::ToString};
+use alloc::{
+    borrow::ToOwned,
+    string::{String, ToString},
+};
 
-use crate::{capitalize, transform};
+use crate::{capitalize, transform, Options, WithOptions};
 
 /// This trait defines a train case conversion.
 ///
@@ -38,11 +41,43 @@ impl ToTrainCase for str {
 /// let sentence = "We are going to inherit the earth.";
 /// assert_eq!(format!("{}", AsTrainCase(sentence)), "We-Are-Going-To-Inherit-The-Earth");
 /// ```
+#[derive(Clone, Copy)]
 pub struct AsTrainCase<T: AsRef<str>>(pub T);
 
+impl<T: AsRef<str>> AsTrainCase<T> {
+    /// Configure this conversion with the given [`Options`].
+    pub fn with_options(self, options: Options) -> WithOptions<Self> {
+        WithOptions {
+            case: self,
+            options,
+        }
+    }
+}
+
 impl<T: AsRef<str>> fmt::Display for AsTrainCase<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        transform(self.0.as_ref(), capitalize, |f| write!(f, "-"), f)
+        fmt::Display::fmt(
+            &AsTrainCase(self.0.as_ref()).with_options(Options::new()),
+            f,
+        )
+    }
+}
+
+impl<T: AsRef<str>> From<AsTrainCase<T>> for String {
+    fn from(case: AsTrainCase<T>) -> String {
+        case.to_string()
+    }
+}
+
+impl<T: AsRef<str>> fmt::Display for WithOptions<'_, AsTrainCase<T>> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        transform(
+            self.case.0.as_ref(),
+            self.options,
+            |s, f| capitalize(s, self.options, f),
+            |_, f| write!(f, "{}", self.options.separator.unwrap_or('-')),
+            f,
+        )
     }
 }
 
@@ -67,7 +102,6 @@ mod tests {
     t!(test6: "SHOUTY_SNAKE_CASE" => "Shouty-Snake-Case");
     t!(test7: "snake_case" => "Snake-Case");
     t!(test8: "this-contains_ ALLKinds OfWord_Boundaries" => "This-Contains-All-Kinds-Of-Word-Boundaries");
-    #[cfg(feature = "unicode")]
     t!(test9: "XΣXΣ baﬄe" => "Xσxς-Baﬄe");
     t!(test10: "XMLHttpRequest" => "Xml-Http-Request");
     t!(test11: "FIELD_NAME11" => "Field-Name11");
@@ -84,4 +118,13 @@ mod tests {
     t!(test23: "ABC123dEEf456FOO" => "Abc123d-E-Ef456-Foo");
     t!(test24: "abcDEF" => "Abc-Def");
     t!(test25: "ABcDE" => "A-Bc-De");
+    t!(test26: "123abc" => "123abc");
+    t!(test27: "room 101b" => "Room-101b");
+    t!(test28: "1st place" => "1st-Place");
+    t!(test29: "22ND_PLACE" => "22nd-Place");
+    t!(test30: "3rd party" => "3rd-Party");
+    t!(test31: "the 4th of july" => "The-4th-Of-July");
+    t!(test32: "v2Point0" => "V2-Point0");
+    t!(test33: "version2point0" => "Version2point0");
+    t!(test34: "x11Server" => "X11-Server");
 }
@@ -5,7 +5,7 @@ use alloc::{
     string::{String, ToString},
 };
 
-use crate::{capitalize, transform};
+use crate::{capitalize, transform, Options, WithOptions};
 
 /// This trait defines an upper camel case conversion.
 ///
@@ -54,23 +54,81 @@ impl<T: ?Sized + ToUpperCamelCase> ToPascalCase for T {
 /// let sentence = "We are not in the least afraid of ruins.";
 /// assert_eq!(format!("{}", AsUpperCamelCase(sentence)), "WeAreNotInTheLeastAfraidOfRuins");
 /// ```
+#[derive(Clone, Copy)]
 pub struct AsUpperCamelCase<T: AsRef<str>>(pub T);
 
+impl<T: AsRef<str>> AsUpperCamelCase<T> {
+    /// Configure this conversion with the given [`Options`].
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use heck::{AsUpperCamelCase, Options};
+    ///
+    /// let options = Options::new().keep_double_underscores(true);
+    /// assert_eq!(format!("{}", AsUpperCamelCase("FOO__BAR_BAZ").with_options(options)), "Foo_BarBaz");
+    /// ```
+    pub fn with_options(self, options: Options) -> WithOptions<Self> {
+        WithOptions {
+            case: self,
+            options,
+        }
+    }
+}
+
 impl<T: AsRef<str>> fmt::Display for AsUpperCamelCase<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        transform(self.0.as_ref(), capitalize, |_| Ok(()), f)
+        fmt::Display::fmt(
+            &AsUpperCamelCase(self.0.as_ref()).with_options(Options::new()),
+            f,
+        )
+    }
+}
+
+impl<T: AsRef<str>> From<AsUpperCamelCase<T>> for String {
+    fn from(case: AsUpperCamelCase<T>) -> String {
+        case.to_string()
+    }
+}
+
+impl<T: AsRef<str>> fmt::Display for WithOptions<'_, AsUpperCamelCase<T>> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let options = self.options;
+        transform(
+            self.case.0.as_ref(),
+            options,
+            |s, f| capitalize(s, options, f),
+            |separator, f| {
+                if options.keep_double_underscores && separator.chars().count() >= 2 {
+                    write!(f, "_")
+                } else {
+                    Ok(())
+                }
+            },
+            f,
+        )
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::ToUpperCamelCase;
+    use super::{AsUpperCamelCase, ToUpperCamelCase};
+    use crate::ToSnakeCase;
+    use crate::{ContractionPolicy, Locale, Options};
+    use alloc::string::{String, ToString};
 
     macro_rules! t {
-        ($t:ident : $s1:expr => $s2:expr) => {
+        ($t:ident : $($s1:expr => $s2:expr),+ $(,)?) => {
             #[test]
             fn $t() {
-                assert_eq!($s1.to_upper_camel_case(), $s2)
+                $(assert_eq!($s1.to_upper_camel_case(), $s2);)+
+            }
+        };
+        ($t:ident : $options:expr ; $($s1:expr => $s2:expr),+ $(,)?) => {
+            #[test]
+            fn $t() {
+                let options = $options;
+                $(assert_eq!(AsUpperCamelCase($s1).with_options(options).to_string(), $s2);)+
             }
         };
     }
@@ -85,4 +143,144 @@ mod tests {
     t!(test8: "this-contains_ ALLKinds OfWord_Boundaries" => "ThisContainsAllKindsOfWordBoundaries");
     t!(test9: "XΣXΣ baﬄe" => "XσxςBaﬄe");
     t!(test10: "XMLHttpRequest" => "XmlHttpRequest");
+    t!(test11: "123abc" => "123abc");
+    t!(test12: "room 101b" => "Room101b");
+
+    t!(keep_double_underscores: Options::new().keep_double_underscores(true);
+        "foo_bar" => "FooBar",
+        "foo__bar" => "Foo_Bar",
+        "FOO___BAR_BAZ" => "Foo_BarBaz",
+        "foo-_bar" => "Foo_Bar",
+        "foo - bar" => "Foo_Bar",
+        "__foo__" => "Foo",
+    );
+
+    t!(keep_double_underscores_default: "foo__bar" => "FooBar");
+
+    t!(preserve_extension: Options::new().preserve_extension(true);
+        "my_file.JSON" => "MyFile.json",
+        "my_file" => "MyFile",
+    );
+
+    t!(merge_single_letter_words: Options::new().merge_single_letter_words(true);
+        "ABC123dEEf456FOO" => "Abc123dEef456Foo",
+        "abc123DEf456" => "Abc123Def456",
+        "ABcDE" => "AbcDe",
+        "aB" => "Ab",
+        "a b c" => "ABC",
+        "XMLHttpRequest" => "XmlHttpRequest",
+    );
+
+    t!(merge_single_letter_words_default: "ABC123dEEf456FOO" => "Abc123dEEf456Foo");
+
+    t!(strip_prefix_and_suffix: Options::new().strip_prefix("gtk_").strip_suffix("_t");
+        "gtk_widget_show" => "WidgetShow",
+        "gtk_orientation_t" => "Orientation",
+        "size_t" => "Size",
+        "g_free" => "GFree",
+        "GTK_WIDGET" => "GtkWidget",
+        "my_gtk_widget" => "MyGtkWidget",
+        "gtk_" => "",
+        "gtk_t" => "T",
+    );
+
+    t!(strip_prefix_and_suffix_default: "gtk_widget_show" => "GtkWidgetShow");
+
+    t!(dutch: Options::new().locale(Locale::Dutch);
+        "ijs_verkoper" => "IJsVerkoper",
+        "wijn_ijs" => "WijnIJs",
+    );
+
+    #[test]
+    fn into_string() {
+        assert_eq!(String::from(AsUpperCamelCase("xml http")), "XmlHttp");
+        assert_eq!(String::from(AsUpperCamelCase(String::from("a_b"))), "AB");
+    }
+
+    #[test]
+    fn round_trip_through_snake_case() {
+        // snake_case which survives a round trip through UpperCamelCase.
+        for s in [
+            "xml_http_request",
+            "x_ml",
+            "a_bc",
+            "ab_c",
+            "io_error",
+            "v2_api",
+            "foo2_bar",
+            "ab_c_de",
+            "σας_οδος",
+        ] {
+            assert_eq!(s.to_upper_camel_case().to_snake_case(), s);
+        }
+
+        // snake_case which does not, and what it becomes.
+        for (s, round_trip) in [
+            ("a_b_c", "abc"),
+            ("a_b", "ab"),
+            ("a_b_cd", "ab_cd"),
+            ("a_x1_b", "ax1b"),
+            ("foo_2", "foo2"),
+            ("foo_2bar", "foo2bar"),
+            ("ßig", "s_sig"),
+            ("hello__world", "hello_world"),
+        ] {
+            assert_eq!(s.to_upper_camel_case().to_snake_case(), round_trip);
+        }
+
+        // UpperCamelCase which survives a round trip through snake_case.
+        for s in [
+            "HttpServer",
+            "XmlHttpRequest",
+            "IoError",
+            "V2Api",
+            "Foo2Bar",
+        ] {
+            assert_eq!(s.to_snake_case().to_upper_camel_case(), s);
+        }
+
+        // UpperCamelCase which does not, and what it becomes.
+        for (s, round_trip) in [
+            ("XMLHttpRequest", "XmlHttpRequest"),
+            ("IOError", "IoError"),
+            ("ParseURL", "ParseUrl"),
+            ("ABCd", "AbCd"),
+        ] {
+            assert_eq!(s.to_snake_case().to_upper_camel_case(), round_trip);
+        }
+    }
+
+    t!(brands: Options::new().brands(&["macOS", "OpenGL"]);
+        "macos_opengl_app" => "macOSOpenGLApp",
+    );
+
+    t!(max_words: Options::new().max_words(2);
+        "xml http request" => "XmlHttp",
+    );
+
+    t!(max_words_brands: Options::new().max_words(2).brands(&["macOS", "OpenGL"]);
+        "macos_opengl_app" => "macOSOpenGL",
+    );
+
+    t!(contractions_split: Options::new().contractions(ContractionPolicy::Split);
+        "don't stop" => "DonTStop",
+    );
+
+    t!(contractions_drop: Options::new().contractions(ContractionPolicy::Drop);
+        "don't stop" => "DontStop",
+    );
+
+    t!(contractions_keep: Options::new().contractions(ContractionPolicy::Keep);
+        "don't stop" => "Don'tStop",
+    );
+
+    t!(final_sigma: Options::new().final_sigma(false);
+        "ΟΔΟΣ_ΣΑΣ" => "ΟδοσΣασ",
+    );
+
+    t!(final_sigma_default: "ΟΔΟΣ_ΣΑΣ" => "ΟδοςΣας");
+
+    t!(final_sigma_dutch: Options::new().final_sigma(false).locale(Locale::Dutch);
+        "ΟΔΟΣ" => "Οδοσ",
+    );
 }